@@ -1,22 +1,53 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128, WasmMsg, Addr, Decimal,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg, Addr, Decimal, BankMsg, Coin, CosmosMsg,
+    Event, Storage,
 };
 use cw2::set_contract_version;
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use lp_events::locker::{self as events, UnlockKind};
+use cw20::{
+    BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Expiration, MinterResponse,
+    TokenInfoResponse,
+};
 
 use crate::error::ContractError;
+use crate::history::{self, LockerEventKind};
+use crate::migrations;
+use crate::ve;
 use crate::msg::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, LockerResponse, LockersResponse,
     QueryMsg, WhitelistedLPResponse, TotalLockedResponse, Cw20HookMsg, MigrateMsg,
+    VotingPowerResponse, LockedBalanceResponse, OperatorResponse, SudoMsg, Asset,
+    PairCw20HookMsg, PairQueryMsg, FactoryQueryMsg, PairInfo, TrustedFactoryResponse,
+    LockedValueResponse, PoolResponse, LockerHistoryResponse, OwnerPortfolioResponse,
+    PortfolioEntry, TierForResponse, ScreeningQueryMsg, ScreeningResponse, BeneficiaryResponse,
+    RewardControllerExecuteMsg, UnlockableLockersResponse,
 };
 use crate::state::{
-    Config, Locker, WhitelistedLP, CONFIG, LOCKERS, USER_LOCKERS, WHITELISTED_LPS, TOTAL_LOCKED,
+    AssetInfo, Config, ConfigBounds, Locker, LockerMetadata, OperatorApproval, OperatorPermission,
+    PauseFlags, WhitelistedLP, LPDefaults, LPPair, LockLimits, LockTier, TrustedFactory, Screening,
+    MAX_LOCK_TIERS,
+    CONFIG, OPERATORS, TRUSTED_FACTORIES, LOCKERS, USER_LOCKERS, WHITELISTED_LPS, TOTAL_LOCKED,
+    LOCKED_BALANCES, MIGRATION_HISTORY, MigrationRecord, OwnerTotals, OWNER_TOTALS,
+    OWNER_LOCKER_COUNT,
+    KEEPER_OPT_OUTS, MATURITY_INDEX, OWNER_MATURITY_INDEX, OWNER_LP_MATURITY_INDEX, BLOCKLIST,
+    Beneficiary,
+    LOCKER_BENEFICIARIES,
+    OWNER_BENEFICIARIES, LAST_ACTIVITY, MAX_INACTIVITY_PERIOD, MIN_INACTIVITY_PERIOD,
+    MAX_BONUS_MULTIPLIER,
+    MAX_EMERGENCY_UNLOCK_DELAY, MAX_PLATFORM_FEE_BPS, MIN_EMERGENCY_UNLOCK_DELAY,
+    MAX_DESCRIPTION_LENGTH, MAX_PROJECT_NAME_LENGTH, MAX_TAGS, MAX_TAG_LENGTH, MAX_URL_LENGTH,
 };
 
 const CONTRACT_NAME: &str = "crates.io:lp-locker";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Maximum number of lockers processed by a single batch message
+const MAX_BATCH_SIZE: usize = 30;
+
+/// Maximum number of index entries `UnlockableLockers` reads per page
+const MAX_UNLOCKABLE_SCAN: usize = 100;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -35,12 +66,18 @@ pub fn instantiate(
         platform_fee_bps: 0, // Can be updated later
         paused: false,
         next_locker_id: 0,
+        pause: PauseFlags::default(),
+        bounds: ConfigBounds::default(),
+        keeper_tip_bps: 0,
+        limits: LockLimits::default(),
+        screening: Screening::default(),
     };
 
+    validate_config(&config)?;
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
-        .add_attribute("method", "instantiate")
+        .add_attribute("action", "instantiate")
         .add_attribute("admin", msg.admin)
         .add_attribute("emergency_unlock_delay", msg.emergency_unlock_delay.to_string()))
 }
@@ -52,15 +89,86 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // Any call by an owner resets the inactivity clock of their beneficiaries
+    let caller = match &msg {
+        ExecuteMsg::Receive(wrapper) => deps.api.addr_validate(&wrapper.sender)?,
+        _ => info.sender.clone(),
+    };
+    record_activity(deps.storage, &env, &caller)?;
+
     match msg {
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::UnlockLP { locker_id } => execute_unlock_lp(deps, env, info, locker_id),
         ExecuteMsg::ExtendLock { locker_id, new_unlock_time } => {
             execute_extend_lock(deps, env, info, locker_id, new_unlock_time)
         }
+        ExecuteMsg::ExtendLockBy { locker_id, additional_seconds } => {
+            execute_extend_lock_by(deps, env, info, locker_id, additional_seconds)
+        }
+        ExecuteMsg::UnlockAndWithdrawLiquidity {
+            locker_id,
+            pair_contract,
+            min_assets,
+        } => execute_unlock_and_withdraw_liquidity(
+            deps,
+            env,
+            info,
+            locker_id,
+            pair_contract,
+            min_assets,
+        ),
+        ExecuteMsg::UnlockMany { locker_ids } => {
+            execute_unlock_many(deps, env, info, locker_ids)
+        }
+        ExecuteMsg::ReleaseMatured { locker_ids } => {
+            execute_release_matured(deps, env, info, locker_ids)
+        }
+        ExecuteMsg::SetKeeperRelease { enabled } => execute_set_keeper_release(deps, info, enabled),
+        ExecuteMsg::ExtendMany { locker_ids, new_unlock_time } => {
+            execute_extend_many(deps, env, info, locker_ids, new_unlock_time)
+        }
+        ExecuteMsg::EnableAutoRelock { locker_id, duration } => {
+            execute_enable_auto_relock(deps, env, info, locker_id, duration)
+        }
+        ExecuteMsg::DisableAutoRelock { locker_id } => {
+            execute_disable_auto_relock(deps, env, info, locker_id)
+        }
+        ExecuteMsg::UpdateLockerMetadata { locker_id, metadata } => {
+            execute_update_locker_metadata(deps, env, info, locker_id, metadata)
+        }
+        ExecuteMsg::TransferLocker { locker_id, recipient } => {
+            execute_transfer_locker(deps, env, info, locker_id, recipient)
+        }
+        ExecuteMsg::ApproveOperator { operator, permissions, expires, transfer_recipients } => {
+            execute_approve_operator(
+                deps,
+                env,
+                info,
+                operator,
+                permissions,
+                expires,
+                transfer_recipients.unwrap_or_default(),
+            )
+        }
+        ExecuteMsg::RevokeOperator { operator } => execute_revoke_operator(deps, info, operator),
         ExecuteMsg::RequestEmergencyUnlock { locker_id } => {
             execute_request_emergency_unlock(deps, env, info, locker_id)
         }
+        ExecuteMsg::CancelEmergencyUnlock { locker_id } => {
+            execute_cancel_emergency_unlock(deps, env, info, locker_id)
+        }
+        ExecuteMsg::SetBeneficiary { locker_id, beneficiary, inactivity_period } => {
+            execute_set_beneficiary(deps, info, locker_id, beneficiary, inactivity_period)
+        }
+        ExecuteMsg::RemoveBeneficiary { locker_id } => {
+            execute_remove_beneficiary(deps, info, locker_id)
+        }
+        ExecuteMsg::Heartbeat {} => Ok(Response::new()
+            .add_attribute("action", "heartbeat")
+            .add_attribute("owner", info.sender)),
+        ExecuteMsg::ClaimInheritance { locker_id } => {
+            execute_claim_inheritance(deps, env, info, locker_id)
+        }
         ExecuteMsg::ExecuteEmergencyUnlock { locker_id } => {
             execute_emergency_unlock(deps, env, info, locker_id)
         }
@@ -69,6 +177,7 @@ pub fn execute(
             reward_controller,
             emergency_unlock_delay,
             platform_fee_bps,
+            keeper_tip_bps,
         } => execute_update_config(
             deps,
             info,
@@ -76,12 +185,15 @@ pub fn execute(
             reward_controller,
             emergency_unlock_delay,
             platform_fee_bps,
+            keeper_tip_bps,
         ),
         ExecuteMsg::WhitelistLP {
             lp_token,
             min_lock_duration,
             max_lock_duration,
             bonus_multiplier,
+            tiers,
+            pair_contract,
         } => execute_whitelist_lp(
             deps,
             info,
@@ -89,10 +201,75 @@ pub fn execute(
             min_lock_duration,
             max_lock_duration,
             bonus_multiplier,
+            tiers,
+            pair_contract,
+        ),
+        ExecuteMsg::WhitelistLPFromFactory { pair_contract } => {
+            execute_whitelist_lp_from_factory(deps, info, pair_contract)
+        }
+        ExecuteMsg::AddTrustedFactory { factory, permissionless } => {
+            execute_add_trusted_factory(deps, info, factory, permissionless)
+        }
+        ExecuteMsg::RemoveTrustedFactory { factory } => {
+            execute_remove_trusted_factory(deps, info, factory)
+        }
+        ExecuteMsg::UpdateLP {
+            lp_token,
+            min_lock_duration,
+            max_lock_duration,
+            bonus_multiplier,
+            tiers,
+            enabled,
+        } => execute_update_lp(
+            deps,
+            info,
+            lp_token,
+            min_lock_duration,
+            max_lock_duration,
+            bonus_multiplier,
+            tiers,
+            enabled,
         ),
+        ExecuteMsg::DisableLP { lp_token } => execute_disable_lp(deps, info, lp_token),
         ExecuteMsg::RemoveLP { lp_token } => execute_remove_lp(deps, info, lp_token),
         ExecuteMsg::Pause {} => execute_pause(deps, info),
         ExecuteMsg::Resume {} => execute_resume(deps, info),
+        ExecuteMsg::SetPauseFlags {
+            locks,
+            extends,
+            emergency_requests,
+            transfers,
+            fee_withdrawal,
+        } => execute_set_pause_flags(
+            deps,
+            info,
+            locks,
+            extends,
+            emergency_requests,
+            transfers,
+            fee_withdrawal,
+        ),
+        ExecuteMsg::SetLPPaused { lp_token, paused } => {
+            execute_set_lp_paused(deps, info, lp_token, paused)
+        }
+        ExecuteMsg::SetLockLimits { lp_token, limits } => {
+            execute_set_lock_limits(deps, info, lp_token, limits)
+        }
+        ExecuteMsg::SetReceiptToken { lp_token, receipt_token } => {
+            execute_set_receipt_token(deps, env, info, lp_token, receipt_token)
+        }
+        ExecuteMsg::RecoverTokens { asset, amount, recipient } => {
+            execute_recover_tokens(deps, env, info, asset, amount, recipient)
+        }
+        ExecuteMsg::UpdateBlocklist { add, remove } => {
+            execute_update_blocklist(deps, info, add, remove)
+        }
+        ExecuteMsg::SetScreening { contract, recovery_address } => {
+            execute_set_screening(deps, info, contract, recovery_address)
+        }
+        ExecuteMsg::RecoverBlockedLockers { locker_ids } => {
+            execute_recover_blocked_lockers(deps, env, info, locker_ids)
+        }
     }
 }
 
@@ -102,13 +279,7 @@ fn execute_receive(
     info: MessageInfo,
     wrapper: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    
-    if config.paused {
-        return Err(ContractError::Paused {});
-    }
-
-    let lp_token = info.sender;
+    let token = info.sender;
     let sender = deps.api.addr_validate(&wrapper.sender)?;
     let amount = wrapper.amount;
 
@@ -120,12 +291,57 @@ fn execute_receive(
     let msg: Cw20HookMsg = from_json(&wrapper.msg)?;
 
     match msg {
-        Cw20HookMsg::LockLP { unlock_time, metadata } => {
-            execute_lock_lp(deps, env, sender, lp_token, amount, unlock_time, metadata)
+        Cw20HookMsg::LockLP { unlock_time, metadata, auto_relock } => {
+            let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, config.pause.locks)?;
+            ensure_not_screened(deps.as_ref(), &config, &sender)?;
+
+            execute_lock_lp(
+                deps,
+                env,
+                sender,
+                token,
+                amount,
+                unlock_time,
+                metadata,
+                auto_relock,
+            )
+        }
+        Cw20HookMsg::LockLPFor { lock_duration, metadata, auto_relock } => {
+            let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, config.pause.locks)?;
+            ensure_not_screened(deps.as_ref(), &config, &sender)?;
+
+            let unlock_time = env.block.time.seconds().saturating_add(lock_duration);
+            execute_lock_lp(
+                deps,
+                env,
+                sender,
+                token,
+                amount,
+                unlock_time,
+                metadata,
+                auto_relock,
+            )
+        }
+        Cw20HookMsg::IncreaseLock { locker_id } => {
+            let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, config.pause.locks)?;
+            ensure_not_screened(deps.as_ref(), &config, &sender)?;
+
+            execute_increase_lock(deps, env, sender, token, amount, locker_id)
+        }
+        // Receipt redemptions are unlocks and therefore never paused
+        Cw20HookMsg::UnlockLP { locker_id } => {
+            execute_redeem_receipt(deps, env, token, sender, amount, locker_id, false)
+        }
+        Cw20HookMsg::ExecuteEmergencyUnlock { locker_id } => {
+            execute_redeem_receipt(deps, env, token, sender, amount, locker_id, true)
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_lock_lp(
     deps: DepsMut,
     env: Env,
@@ -133,8 +349,13 @@ fn execute_lock_lp(
     lp_token: Addr,
     amount: Uint128,
     unlock_time: u64,
-    metadata: Option<String>,
+    metadata: Option<LockerMetadata>,
+    auto_relock: Option<u64>,
 ) -> Result<Response, ContractError> {
+    if let Some(metadata) = &metadata {
+        validate_metadata(metadata)?;
+    }
+
     // Validate LP token is whitelisted
     let whitelist = WHITELISTED_LPS
         .may_load(deps.storage, &lp_token)?
@@ -144,6 +365,10 @@ fn execute_lock_lp(
         return Err(ContractError::LPNotWhitelisted {});
     }
 
+    if whitelist.paused {
+        return Err(ContractError::LPPaused {});
+    }
+
     // Validate unlock time
     let current_time = env.block.time.seconds();
     let lock_duration = unlock_time.checked_sub(current_time)
@@ -159,8 +384,21 @@ fn execute_lock_lp(
         });
     }
 
-    // Create locker
+    if let Some(duration) = auto_relock {
+        validate_relock_duration(&whitelist, duration)?;
+    }
+
     let mut config = CONFIG.load(deps.storage)?;
+    let limits = whitelist.limits.or(&config.limits);
+    if let Some(min) = limits.min_lock_amount {
+        if amount < min {
+            return Err(ContractError::BelowMinLockAmount { min });
+        }
+    }
+    ensure_within_lock_cap(deps.storage, &limits, &lp_token, amount)?;
+    ensure_locker_slot(deps.storage, &whitelist.limits, &config.limits, &sender, &lp_token)?;
+
+    // Create locker
     let locker_id = config.next_locker_id;
     config.next_locker_id += 1;
     CONFIG.save(deps.storage, &config)?;
@@ -175,21 +413,43 @@ fn execute_lock_lp(
         extended_count: 0,
         emergency_unlock_requested: None,
         metadata,
+        auto_relock,
+        // A rolling lock always sits at least its relock duration away
+        bonus_multiplier: tier_multiplier(&whitelist, lock_duration.max(auto_relock.unwrap_or(0))),
     };
 
     LOCKERS.save(deps.storage, locker_id, &locker)?;
     USER_LOCKERS.save(deps.storage, (&sender, locker_id), &true)?;
-
-    // Update total locked
-    TOTAL_LOCKED.update(
+    ve::checkpoint_locker(deps.storage, current_time, &locker, Some(whitelist.max_lock_duration))?;
+    history::record(
         deps.storage,
-        &lp_token,
-        |total| -> StdResult<_> {
-            Ok(total.unwrap_or_default().checked_add(amount)?)
+        &env,
+        locker_id,
+        LockerEventKind::Created {
+            owner: sender.clone(),
+            amount,
+            unlock_time,
         },
     )?;
 
+    // Update total locked
+    increase_locked(deps.storage, env.block.height, &sender, &lp_token, amount)?;
+    add_owner_totals(deps.storage, &locker)?;
+    index_maturity(deps.storage, &locker)?;
+
+    // Mint the transferable receipt for the locked principal
+    let mint_msg = mint_receipt_msg(&whitelist, &sender, amount)?;
+
     Ok(Response::new()
+        .add_messages(mint_msg)
+        .add_event(Event::from(events::Locked {
+            locker_id,
+            owner: sender.clone(),
+            lp_token: lp_token.clone(),
+            amount,
+            unlock_time,
+            auto_relock,
+        }))
         .add_attribute("action", "lock_lp")
         .add_attribute("locker_id", locker_id.to_string())
         .add_attribute("owner", sender)
@@ -198,133 +458,373 @@ fn execute_lock_lp(
         .add_attribute("unlock_time", unlock_time.to_string()))
 }
 
-fn execute_unlock_lp(
+fn execute_increase_lock(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    sender: Addr,
+    lp_token: Addr,
+    amount: Uint128,
     locker_id: u64,
 ) -> Result<Response, ContractError> {
-    let locker = LOCKERS.load(deps.storage, locker_id)?;
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
 
-    // Verify owner
-    if locker.owner != info.sender {
-        return Err(ContractError::NotOwner {});
+    ensure_authorized(deps.as_ref(), &env, &locker, &sender, OperatorPermission::Increase)?;
+
+    if locker.lp_token != lp_token {
+        return Err(ContractError::TokenMismatch {});
     }
 
-    // Check unlock time (NOT affected by pause)
-    let current_time = env.block.time.seconds();
-    if current_time < locker.unlock_time {
-        return Err(ContractError::StillLocked(locker.unlock_time));
+    // A top-up is a new lock of principal, so it follows the same whitelist rules
+    let whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &lp_token)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    if !whitelist.enabled {
+        return Err(ContractError::LPNotWhitelisted {});
+    }
+
+    if whitelist.paused {
+        return Err(ContractError::LPPaused {});
     }
 
-    // Remove locker
-    LOCKERS.remove(deps.storage, locker_id);
-    USER_LOCKERS.remove(deps.storage, (&locker.owner, locker_id));
+    // Top-ups add no locker, so only the LP cap applies
+    let config = CONFIG.load(deps.storage)?;
+    let limits = whitelist.limits.or(&config.limits);
+    ensure_within_lock_cap(deps.storage, &limits, &lp_token, amount)?;
 
-    // Update total locked
-    TOTAL_LOCKED.update(
+    remove_owner_totals(deps.storage, &locker)?;
+    locker.amount = locker.amount.checked_add(amount)?;
+    add_owner_totals(deps.storage, &locker)?;
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    history::record(
         deps.storage,
-        &locker.lp_token,
-        |total| -> StdResult<_> {
-            Ok(total.unwrap_or_default().checked_sub(locker.amount)?)
+        &env,
+        locker_id,
+        LockerEventKind::ToppedUp {
+            amount,
+            new_amount: locker.amount,
         },
     )?;
+    ve::checkpoint_locker(
+        deps.storage,
+        env.block.time.seconds(),
+        &locker,
+        Some(whitelist.max_lock_duration),
+    )?;
+
+    increase_locked(deps.storage, env.block.height, &locker.owner, &lp_token, amount)?;
+
+    // Receipts always go to the owner, whoever paid for the top-up
+    let mint_msg = mint_receipt_msg(&whitelist, &locker.owner, amount)?;
+
+    Ok(Response::new()
+        .add_messages(mint_msg)
+        .add_event(Event::from(events::ToppedUp {
+            locker_id,
+            owner: locker.owner.clone(),
+            lp_token,
+            amount,
+            new_amount: locker.amount,
+        }))
+        .add_attribute("action", "increase_lock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("sender", sender)
+        .add_attribute("amount", amount)
+        .add_attribute("new_amount", locker.amount))
+}
+
+fn execute_unlock_lp(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let closed_msg = lockers_closed_msg(deps.storage, vec![locker_id])?;
+    let locker = release_locker(deps, &env, &info.sender, locker_id, false)?;
+
+    Ok(Response::new()
+        .add_message(transfer_lp_msg(&locker)?)
+        .add_messages(closed_msg)
+        .add_event(unlocked_event(&locker, UnlockKind::Matured))
+        .add_attribute("action", "unlock_lp")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("owner", locker.owner)
+        .add_attribute("amount", locker.amount))
+}
+
+fn execute_unlock_and_withdraw_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+    pair_contract: String,
+    min_assets: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let pair_contract = deps.api.addr_validate(&pair_contract)?;
+    let locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    // Only the verified pair of the LP may receive it
+    let pair = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .and_then(|whitelist| whitelist.pair)
+        .ok_or(ContractError::UnverifiedLP {})?;
+    if pair.pair_contract != pair_contract {
+        return Err(ContractError::PairMismatch {});
+    }
 
-    // Transfer LP tokens back
-    let transfer_msg = WasmMsg::Execute {
+    let closed_msg = lockers_closed_msg(deps.storage, vec![locker_id])?;
+    let locker = release_loaded_locker(deps, &env, &info.sender, locker, false)?;
+
+    // The pair burns the LP and pays the owner directly; `min_assets` guards slippage
+    let withdraw_msg = WasmMsg::Execute {
         contract_addr: locker.lp_token.to_string(),
-        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: locker.owner.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Send {
+            contract: pair_contract.to_string(),
             amount: locker.amount,
+            msg: to_json_binary(&PairCw20HookMsg::WithdrawLiquidity {
+                min_assets,
+                receiver: Some(locker.owner.to_string()),
+            })?,
         })?,
         funds: vec![],
     };
 
     Ok(Response::new()
-        .add_message(transfer_msg)
-        .add_attribute("action", "unlock_lp")
+        .add_message(withdraw_msg)
+        .add_messages(closed_msg)
+        .add_event(unlocked_event(&locker, UnlockKind::Matured))
+        .add_attribute("action", "unlock_and_withdraw_liquidity")
         .add_attribute("locker_id", locker_id.to_string())
         .add_attribute("owner", locker.owner)
+        .add_attribute("pair_contract", pair_contract)
         .add_attribute("amount", locker.amount))
 }
 
-fn execute_extend_lock(
-    deps: DepsMut,
+fn execute_unlock_many(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    locker_id: u64,
-    new_unlock_time: u64,
+    locker_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
-    let mut locker = LOCKERS.load(deps.storage, locker_id)?;
-
-    if locker.owner != info.sender {
-        return Err(ContractError::NotOwner {});
+    validate_batch(&locker_ids)?;
+
+    // Group released amounts per LP token so each token gets a single transfer
+    let mut totals: BTreeMap<Addr, Uint128> = BTreeMap::new();
+    let mut unlocked = Vec::with_capacity(locker_ids.len());
+    for locker_id in &locker_ids {
+        let locker = release_locker(deps.branch(), &env, &info.sender, *locker_id, false)
+            .map_err(|err| batch_error(*locker_id, err))?;
+        unlocked.push(unlocked_event(&locker, UnlockKind::Matured));
+        let total = totals.entry(locker.lp_token).or_default();
+        *total = total.checked_add(locker.amount)?;
     }
 
-    if new_unlock_time <= locker.unlock_time {
-        return Err(ContractError::InvalidExtension {});
+    let mut messages = Vec::with_capacity(totals.len());
+    for (lp_token, amount) in totals {
+        messages.push(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        });
     }
 
-    // Validate against whitelist
-    let whitelist = WHITELISTED_LPS.load(deps.storage, &locker.lp_token)?;
-    let current_time = env.block.time.seconds();
-    let new_duration = new_unlock_time.checked_sub(current_time).unwrap_or(0);
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_messages(lockers_closed_msg(deps.storage, locker_ids.clone())?)
+        .add_events(unlocked)
+        .add_attribute("action", "unlock_many")
+        .add_attribute("owner", info.sender)
+        .add_attribute("locker_ids", join_ids(&locker_ids)))
+}
 
-    if new_duration > whitelist.max_lock_duration {
-        return Err(ContractError::InvalidUnlockTime {
-            min: whitelist.min_lock_duration,
-            max: whitelist.max_lock_duration,
-        });
-    }
+fn execute_release_matured(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    validate_batch(&locker_ids)?;
+    let config = CONFIG.load(deps.storage)?;
 
-    let old_unlock_time = locker.unlock_time;
-    locker.unlock_time = new_unlock_time;
-    locker.extended_count += 1;
+    let mut messages = Vec::with_capacity(locker_ids.len() + 1);
+    let mut tips: BTreeMap<Addr, Uint128> = BTreeMap::new();
+    let mut tip_funds: BTreeMap<Addr, Uint128> = BTreeMap::new();
+    let mut released_lp: BTreeMap<Addr, Uint128> = BTreeMap::new();
+    let mut released = Vec::with_capacity(locker_ids.len());
+    for locker_id in &locker_ids {
+        let locker = keeper_release_locker(deps.branch(), &env, *locker_id)
+            .map_err(|err| batch_error(*locker_id, err))?;
+        messages.push(transfer_lp_msg(&locker)?);
+        let total = released_lp.entry(locker.lp_token.clone()).or_default();
+        *total = total.checked_add(locker.amount)?;
+
+        // Owners releasing their own lockers pay no tip
+        if locker.owner == info.sender {
+            released.push(unlocked_event(&locker, UnlockKind::Matured));
+            continue;
+        }
 
-    LOCKERS.save(deps.storage, locker_id, &locker)?;
+        // Tips come out of platform funds, never out of anyone's LP. Everything
+        // released so far in this batch is still in the balance but no longer
+        // in the total, so it is taken off the surplus.
+        let funds = match tip_funds.entry(locker.lp_token.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let surplus = lp_surplus(deps.as_ref(), &env, &locker.lp_token)?;
+                entry.insert(surplus.saturating_sub(released_lp[&locker.lp_token]))
+            }
+        };
+        let tip = locker
+            .amount
+            .multiply_ratio(config.keeper_tip_bps, 10_000u128)
+            .min(*funds);
+        *funds -= tip;
+
+        released.push(Event::from(events::Unlocked {
+            tip,
+            ..unlocked(&locker, UnlockKind::Keeper)
+        }));
+        if !tip.is_zero() {
+            let total = tips.entry(locker.lp_token).or_default();
+            *total = total.checked_add(tip)?;
+        }
+    }
+
+    for (lp_token, amount) in tips {
+        messages.push(cw20_transfer_msg(&lp_token, &info.sender, amount)?);
+    }
+    messages.extend(lockers_closed_msg(deps.storage, locker_ids.clone())?);
 
     Ok(Response::new()
-        .add_attribute("action", "extend_lock")
-        .add_attribute("locker_id", locker_id.to_string())
-        .add_attribute("old_unlock_time", old_unlock_time.to_string())
-        .add_attribute("new_unlock_time", new_unlock_time.to_string()))
+        .add_messages(messages)
+        .add_events(released)
+        .add_attribute("action", "release_matured")
+        .add_attribute("keeper", info.sender)
+        .add_attribute("locker_ids", join_ids(&locker_ids)))
 }
 
-fn execute_request_emergency_unlock(
+fn execute_set_keeper_release(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    locker_id: u64,
+    enabled: bool,
 ) -> Result<Response, ContractError> {
-    let mut locker = LOCKERS.load(deps.storage, locker_id)?;
+    if enabled {
+        KEEPER_OPT_OUTS.remove(deps.storage, &info.sender);
+    } else {
+        KEEPER_OPT_OUTS.save(deps.storage, &info.sender, &true)?;
+    }
 
-    if locker.owner != info.sender {
+    Ok(Response::new()
+        .add_attribute("action", "set_keeper_release")
+        .add_attribute("owner", info.sender)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// Validates that `sender` may unlock the locker and removes it from storage.
+/// The caller is responsible for transferring the returned amount.
+fn release_locker(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    locker_id: u64,
+    receipt_returned: bool,
+) -> Result<Locker, ContractError> {
+    let locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    release_loaded_locker(deps, env, sender, locker, receipt_returned)
+}
+
+/// `release_locker` for a locker the caller has already loaded
+fn release_loaded_locker(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    locker: Locker,
+    receipt_returned: bool,
+) -> Result<Locker, ContractError> {
+    // Verify owner
+    if locker.owner != *sender {
         return Err(ContractError::NotOwner {});
     }
+    ensure_not_blocked(deps.storage, &locker.owner)?;
 
-    let config = CONFIG.load(deps.storage)?;
-    let execute_at = env.block.time.seconds() + config.emergency_unlock_delay;
+    release_matured_locker(deps, env, locker, receipt_returned)
+}
 
-    locker.emergency_unlock_requested = Some(execute_at);
-    LOCKERS.save(deps.storage, locker_id, &locker)?;
+/// Keeper counterpart of `release_locker`, for owners who have not opted out
+fn keeper_release_locker(
+    deps: DepsMut,
+    env: &Env,
+    locker_id: u64,
+) -> Result<Locker, ContractError> {
+    let locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
 
-    Ok(Response::new()
-        .add_attribute("action", "request_emergency_unlock")
-        .add_attribute("locker_id", locker_id.to_string())
-        .add_attribute("execute_at", execute_at.to_string()))
+    if KEEPER_OPT_OUTS.has(deps.storage, &locker.owner) {
+        return Err(ContractError::KeeperReleaseDisabled {});
+    }
+    ensure_not_blocked(deps.storage, &locker.owner)?;
+
+    release_matured_locker(deps, env, locker, false)
 }
 
-fn execute_emergency_unlock(
+fn release_matured_locker(
     deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
+    env: &Env,
+    locker: Locker,
+    receipt_returned: bool,
+) -> Result<Locker, ContractError> {
+    if locker.auto_relock.is_some() {
+        return Err(ContractError::AutoRelockEnabled {});
+    }
+
+    // Check unlock time (NOT affected by pause)
+    let current_time = env.block.time.seconds();
+    if current_time < locker.unlock_time {
+        return Err(ContractError::StillLocked(locker.unlock_time));
+    }
+
+    ensure_receipt_returned(deps.as_ref(), &locker, receipt_returned)?;
+    history::record(
+        deps.storage,
+        env,
+        locker.id,
+        LockerEventKind::Unlocked {
+            amount: locker.amount,
+            emergency: false,
+        },
+    )?;
+    remove_locker(deps, env, &locker)?;
+
+    Ok(locker)
+}
+
+/// Emergency counterpart of `release_locker`, checked against the requested delay
+fn emergency_release_locker(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
     locker_id: u64,
-) -> Result<Response, ContractError> {
-    let locker = LOCKERS.load(deps.storage, locker_id)?;
+    receipt_returned: bool,
+) -> Result<Locker, ContractError> {
+    let locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
 
-    if locker.owner != info.sender {
+    if locker.owner != *sender {
         return Err(ContractError::NotOwner {});
     }
+    ensure_not_blocked(deps.storage, &locker.owner)?;
 
     let execute_at = locker.emergency_unlock_requested
         .ok_or(ContractError::EmergencyNotRequested {})?;
@@ -333,309 +833,2649 @@ fn execute_emergency_unlock(
         return Err(ContractError::EmergencyDelayNotPassed(execute_at));
     }
 
-    // Remove locker
-    LOCKERS.remove(deps.storage, locker_id);
-    USER_LOCKERS.remove(deps.storage, (&locker.owner, locker_id));
+    ensure_receipt_returned(deps.as_ref(), &locker, receipt_returned)?;
+    history::record(
+        deps.storage,
+        env,
+        locker_id,
+        LockerEventKind::Unlocked {
+            amount: locker.amount,
+            emergency: true,
+        },
+    )?;
+    remove_locker(deps, env, &locker)?;
+
+    Ok(locker)
+}
+
+/// Hands `locker` to `recipient`, moving voting power and balances with it
+fn move_locker(
+    storage: &mut dyn Storage,
+    env: &Env,
+    locker: &mut Locker,
+    recipient: &Addr,
+    whitelist: &WhitelistedLP,
+    kind: LockerEventKind,
+) -> StdResult<()> {
+    let current_time = env.block.time.seconds();
+    ve::checkpoint_locker(storage, current_time, locker, None)?;
+    decrease_locked(storage, env.block.height, &locker.owner, &locker.lp_token, locker.amount)?;
+    USER_LOCKERS.remove(storage, (&locker.owner, locker.id));
+    remove_owner_totals(storage, locker)?;
+    unindex_maturity(storage, locker);
+    LOCKER_BENEFICIARIES.remove(storage, locker.id);
+
+    // A pending emergency request belongs to the previous owner
+    if locker.emergency_unlock_requested.take().is_some() {
+        history::record(storage, env, locker.id, LockerEventKind::EmergencyCancelled {})?;
+    }
+    locker.owner = recipient.clone();
+    history::record(storage, env, locker.id, kind)?;
+
+    LOCKERS.save(storage, locker.id, locker)?;
+    USER_LOCKERS.save(storage, (recipient, locker.id), &true)?;
+    increase_locked(storage, env.block.height, recipient, &locker.lp_token, locker.amount)?;
+    add_owner_totals(storage, locker)?;
+    index_maturity(storage, locker)?;
+    ve::checkpoint_locker(storage, current_time, locker, Some(whitelist.max_lock_duration))
+}
+
+fn remove_locker(deps: DepsMut, env: &Env, locker: &Locker) -> StdResult<()> {
+    LOCKERS.remove(deps.storage, locker.id);
+    USER_LOCKERS.remove(deps.storage, (&locker.owner, locker.id));
+    LOCKER_BENEFICIARIES.remove(deps.storage, locker.id);
+    remove_owner_totals(deps.storage, locker)?;
+    unindex_maturity(deps.storage, locker);
+    ve::checkpoint_locker(deps.storage, env.block.time.seconds(), locker, None)?;
 
     // Update total locked
-    TOTAL_LOCKED.update(
+    decrease_locked(
         deps.storage,
+        env.block.height,
+        &locker.owner,
         &locker.lp_token,
-        |total| -> StdResult<_> {
-            Ok(total.unwrap_or_default().checked_sub(locker.amount)?)
-        },
+        locker.amount,
     )?;
 
-    // Transfer LP tokens back
-    let transfer_msg = WasmMsg::Execute {
-        contract_addr: locker.lp_token.to_string(),
-        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: locker.owner.to_string(),
-            amount: locker.amount,
-        })?,
-        funds: vec![],
-    };
-
-    Ok(Response::new()
-        .add_message(transfer_msg)
-        .add_attribute("action", "emergency_unlock")
-        .add_attribute("locker_id", locker_id.to_string()))
+    Ok(())
 }
 
-fn execute_update_config(
+fn execute_redeem_receipt(
     deps: DepsMut,
-    info: MessageInfo,
-    admin: Option<String>,
-    reward_controller: Option<String>,
-    emergency_unlock_delay: Option<u64>,
-    platform_fee_bps: Option<u16>,
+    env: Env,
+    receipt_token: Addr,
+    sender: Addr,
+    amount: Uint128,
+    locker_id: u64,
+    emergency: bool,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+    let locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
 
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+    if load_receipt_token(deps.as_ref(), &locker.lp_token)? != Some(receipt_token.clone()) {
+        return Err(ContractError::InvalidReceipt {});
     }
 
-    if let Some(admin_addr) = admin {
+    if amount != locker.amount {
+        return Err(ContractError::InvalidReceiptAmount {
+            expected: locker.amount,
+        });
+    }
+
+    let closed_msg = lockers_closed_msg(deps.storage, vec![locker_id])?;
+    let locker = if emergency {
+        emergency_release_locker(deps, &env, &sender, locker_id, true)?
+    } else {
+        release_locker(deps, &env, &sender, locker_id, true)?
+    };
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: receipt_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    };
+
+    let kind = if emergency { UnlockKind::Emergency } else { UnlockKind::Matured };
+
+    Ok(Response::new()
+        .add_message(burn_msg)
+        .add_message(transfer_lp_msg(&locker)?)
+        .add_messages(closed_msg)
+        .add_event(unlocked_event(&locker, kind))
+        .add_attribute("action", if emergency { "emergency_unlock" } else { "unlock_lp" })
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("owner", locker.owner)
+        .add_attribute("amount", locker.amount)
+        .add_attribute("receipt_burned", amount))
+}
+
+fn execute_extend_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+    new_unlock_time: u64,
+) -> Result<Response, ContractError> {
+    let extended = extend_locker(deps, &env, &info.sender, locker_id, new_unlock_time)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "extend_lock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("old_unlock_time", extended.old_unlock_time.to_string())
+        .add_attribute("new_unlock_time", new_unlock_time.to_string())
+        .add_event(Event::from(extended)))
+}
+
+fn execute_extend_lock_by(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+    additional_seconds: u64,
+) -> Result<Response, ContractError> {
+    let locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    let new_unlock_time = locker
+        .unlock_time
+        .max(env.block.time.seconds())
+        .saturating_add(additional_seconds);
+    let extended = extend_locker(deps, &env, &info.sender, locker_id, new_unlock_time)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "extend_lock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("old_unlock_time", extended.old_unlock_time.to_string())
+        .add_attribute("new_unlock_time", new_unlock_time.to_string())
+        .add_event(Event::from(extended)))
+}
+
+fn execute_extend_many(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_ids: Vec<u64>,
+    new_unlock_time: u64,
+) -> Result<Response, ContractError> {
+    validate_batch(&locker_ids)?;
+
+    let mut extended = Vec::with_capacity(locker_ids.len());
+    for locker_id in &locker_ids {
+        let event = extend_locker(deps.branch(), &env, &info.sender, *locker_id, new_unlock_time)
+            .map_err(|err| batch_error(*locker_id, err))?;
+        extended.push(Event::from(event));
+    }
+
+    Ok(Response::new()
+        .add_events(extended)
+        .add_attribute("action", "extend_many")
+        .add_attribute("owner", info.sender)
+        .add_attribute("locker_ids", join_ids(&locker_ids))
+        .add_attribute("new_unlock_time", new_unlock_time.to_string()))
+}
+
+/// Moves the locker's unlock time forward and returns the change as an event.
+fn extend_locker(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    locker_id: u64,
+    new_unlock_time: u64,
+) -> Result<events::Extended, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    ensure_authorized(deps.as_ref(), env, &locker, sender, OperatorPermission::Extend)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure_not_paused(&config, config.pause.extends)?;
+
+    if locker.auto_relock.is_some() {
+        return Err(ContractError::AutoRelockEnabled {});
+    }
+
+    if new_unlock_time <= locker.unlock_time {
+        return Err(ContractError::InvalidExtension {});
+    }
+
+    // Validate against whitelist
+    let whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+    if whitelist.paused {
+        return Err(ContractError::LPPaused {});
+    }
+
+    let current_time = env.block.time.seconds();
+    let new_duration = new_unlock_time.saturating_sub(current_time);
+
+    if new_duration > whitelist.max_lock_duration {
+        return Err(ContractError::InvalidUnlockTime {
+            min: whitelist.min_lock_duration,
+            max: whitelist.max_lock_duration,
+        });
+    }
+
+    let old_unlock_time = locker.unlock_time;
+    unindex_maturity(deps.storage, &locker);
+    locker.unlock_time = new_unlock_time;
+    locker.extended_count += 1;
+    locker.bonus_multiplier = tier_multiplier(&whitelist, new_duration);
+    index_maturity(deps.storage, &locker)?;
+
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    ve::checkpoint_locker(deps.storage, current_time, &locker, Some(whitelist.max_lock_duration))?;
+    history::record(
+        deps.storage,
+        env,
+        locker_id,
+        LockerEventKind::Extended {
+            old_unlock_time,
+            new_unlock_time,
+        },
+    )?;
+
+    Ok(events::Extended {
+        locker_id,
+        owner: locker.owner,
+        lp_token: locker.lp_token,
+        old_unlock_time,
+        new_unlock_time,
+    })
+}
+
+fn execute_enable_auto_relock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    ensure_authorized(deps.as_ref(), &env, &locker, &info.sender, OperatorPermission::Extend)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure_not_paused(&config, config.pause.extends)?;
+
+    let whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    if whitelist.paused {
+        return Err(ContractError::LPPaused {});
+    }
+
+    validate_relock_duration(&whitelist, duration)?;
+
+    unindex_maturity(deps.storage, &locker);
+    locker.auto_relock = Some(duration);
+    let current_time = env.block.time.seconds();
+    locker.bonus_multiplier = tier_multiplier(
+        &whitelist,
+        effective_unlock_time(&locker, current_time) - current_time,
+    );
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    ve::checkpoint_locker(deps.storage, current_time, &locker, Some(whitelist.max_lock_duration))?;
+    history::record(
+        deps.storage,
+        &env,
+        locker_id,
+        LockerEventKind::AutoRelockEnabled { duration },
+    )?;
+
+    Ok(Response::new()
+        .add_event(Event::from(events::AutoRelockChanged {
+            locker_id,
+            owner: locker.owner,
+            lp_token: locker.lp_token,
+            duration: Some(duration),
+            unlock_time: locker.unlock_time,
+        }))
+        .add_attribute("action", "enable_auto_relock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("duration", duration.to_string()))
+}
+
+fn execute_disable_auto_relock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    if locker.owner != info.sender {
+        return Err(ContractError::NotOwner {});
+    }
+
+    // The countdown starts now and never shortens the original commitment
+    let current_time = env.block.time.seconds();
+    locker.unlock_time = effective_unlock_time(&locker, current_time);
+    if locker.auto_relock.take().is_none() {
+        return Err(ContractError::AutoRelockDisabled {});
+    }
+    index_maturity(deps.storage, &locker)?;
+
+    let whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    ve::checkpoint_locker(deps.storage, current_time, &locker, Some(whitelist.max_lock_duration))?;
+    history::record(
+        deps.storage,
+        &env,
+        locker_id,
+        LockerEventKind::AutoRelockDisabled {
+            unlock_time: locker.unlock_time,
+        },
+    )?;
+
+    // A staked rolling locker counted at its full duration until now
+    let update_msg = reward_controller_msg(
+        deps.storage,
+        &RewardControllerExecuteMsg::LockerUpdated { locker_id },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(update_msg)
+        .add_event(Event::from(events::AutoRelockChanged {
+            locker_id,
+            owner: locker.owner,
+            lp_token: locker.lp_token,
+            duration: None,
+            unlock_time: locker.unlock_time,
+        }))
+        .add_attribute("action", "disable_auto_relock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("unlock_time", locker.unlock_time.to_string()))
+}
+
+fn execute_update_locker_metadata(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+    metadata: Option<LockerMetadata>,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    if locker.owner != info.sender {
+        return Err(ContractError::NotOwner {});
+    }
+
+    if let Some(metadata) = &metadata {
+        validate_metadata(metadata)?;
+    }
+
+    locker.metadata = metadata;
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    history::record(deps.storage, &env, locker_id, LockerEventKind::MetadataUpdated {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_locker_metadata")
+        .add_attribute("locker_id", locker_id.to_string()))
+}
+
+fn execute_transfer_locker(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    ensure_authorized(deps.as_ref(), &env, &locker, &info.sender, OperatorPermission::Transfer)?;
+
+    // Operators can only move lockers to addresses the owner named up front
+    if locker.owner != info.sender {
+        let approval = OPERATORS.load(deps.storage, (&locker.owner, &info.sender))?;
+        if !approval.transfer_recipients.contains(&recipient) {
+            return Err(ContractError::RecipientNotApproved {});
+        }
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure_not_paused(&config, config.pause.transfers)?;
+
+    let whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    if whitelist.paused {
+        return Err(ContractError::LPPaused {});
+    }
+
+    ensure_not_blocked(deps.storage, &locker.owner)?;
+    ensure_not_screened(deps.as_ref(), &config, &recipient)?;
+    ensure_locker_slot(
+        deps.storage,
+        &whitelist.limits,
+        &config.limits,
+        &recipient,
+        &locker.lp_token,
+    )?;
+    let previous_owner = locker.owner.clone();
+    let kind = LockerEventKind::Transferred {
+        from: previous_owner.clone(),
+        to: recipient.clone(),
+    };
+    move_locker(deps.storage, &env, &mut locker, &recipient, &whitelist, kind)?;
+
+    // The previous owner's reward stake must not outlive the transfer
+    let unstake_msg = reward_controller_msg(
+        deps.storage,
+        &RewardControllerExecuteMsg::LockerTransferred { locker_id },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(unstake_msg)
+        .add_event(Event::from(events::Transferred {
+            locker_id,
+            lp_token: locker.lp_token,
+            amount: locker.amount,
+            from: previous_owner.clone(),
+            to: recipient.clone(),
+        }))
+        .add_attribute("action", "transfer_locker")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("from", previous_owner)
+        .add_attribute("to", recipient))
+}
+
+fn execute_approve_operator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    permissions: Vec<OperatorPermission>,
+    expires: Option<Expiration>,
+    transfer_recipients: Vec<String>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+
+    if operator == info.sender {
+        return Err(ContractError::InvalidOperator {});
+    }
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidOperator {});
+    }
+
+    let mut permissions = permissions;
+    permissions.sort();
+    permissions.dedup();
+
+    let mut transfer_recipients = transfer_recipients
+        .iter()
+        .map(|recipient| deps.api.addr_validate(recipient))
+        .collect::<StdResult<Vec<_>>>()?;
+    transfer_recipients.sort();
+    transfer_recipients.dedup();
+
+    if permissions.contains(&OperatorPermission::Transfer) == transfer_recipients.is_empty() {
+        return Err(ContractError::InvalidTransferRecipients {});
+    }
+
+    OPERATORS.save(
+        deps.storage,
+        (&info.sender, &operator),
+        &OperatorApproval {
+            permissions: permissions.clone(),
+            expires,
+            transfer_recipients,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_operator")
+        .add_attribute("owner", info.sender)
+        .add_attribute("operator", operator)
+        .add_attribute("expires", expires.to_string()))
+}
+
+fn execute_revoke_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_operator")
+        .add_attribute("owner", info.sender)
+        .add_attribute("operator", operator))
+}
+
+fn execute_request_emergency_unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS.load(deps.storage, locker_id)?;
+
+    if locker.owner != info.sender {
+        return Err(ContractError::NotOwner {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure_not_paused(&config, config.pause.emergency_requests)?;
+
+    let lp_paused = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .is_some_and(|whitelist| whitelist.paused);
+    if lp_paused {
+        return Err(ContractError::LPPaused {});
+    }
+
+    let execute_at = env.block.time.seconds() + config.emergency_unlock_delay;
+
+    remove_owner_totals(deps.storage, &locker)?;
+    locker.emergency_unlock_requested = Some(execute_at);
+    add_owner_totals(deps.storage, &locker)?;
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    history::record(
+        deps.storage,
+        &env,
+        locker_id,
+        LockerEventKind::EmergencyRequested { execute_at },
+    )?;
+
+    Ok(Response::new()
+        .add_event(Event::from(events::EmergencyChanged {
+            locker_id,
+            owner: locker.owner,
+            lp_token: locker.lp_token,
+            amount: locker.amount,
+            execute_at: Some(execute_at),
+        }))
+        .add_attribute("action", "request_emergency_unlock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("execute_at", execute_at.to_string()))
+}
+
+fn execute_cancel_emergency_unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    if locker.owner != info.sender {
+        return Err(ContractError::NotOwner {});
+    }
+
+    if locker.emergency_unlock_requested.is_none() {
+        return Err(ContractError::EmergencyNotRequested {});
+    }
+
+    remove_owner_totals(deps.storage, &locker)?;
+    locker.emergency_unlock_requested = None;
+    add_owner_totals(deps.storage, &locker)?;
+    LOCKERS.save(deps.storage, locker_id, &locker)?;
+    history::record(deps.storage, &env, locker_id, LockerEventKind::EmergencyCancelled {})?;
+
+    Ok(Response::new()
+        .add_event(Event::from(events::EmergencyChanged {
+            locker_id,
+            owner: locker.owner,
+            lp_token: locker.lp_token,
+            amount: locker.amount,
+            execute_at: None,
+        }))
+        .add_attribute("action", "cancel_emergency_unlock")
+        .add_attribute("locker_id", locker_id.to_string()))
+}
+
+fn execute_emergency_unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let closed_msg = lockers_closed_msg(deps.storage, vec![locker_id])?;
+    let locker = emergency_release_locker(deps, &env, &info.sender, locker_id, false)?;
+
+    Ok(Response::new()
+        .add_message(transfer_lp_msg(&locker)?)
+        .add_messages(closed_msg)
+        .add_event(unlocked_event(&locker, UnlockKind::Emergency))
+        .add_attribute("action", "emergency_unlock")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("owner", locker.owner)
+        .add_attribute("amount", locker.amount))
+}
+
+fn execute_set_beneficiary(
+    deps: DepsMut,
+    info: MessageInfo,
+    locker_id: Option<u64>,
+    beneficiary: String,
+    inactivity_period: u64,
+) -> Result<Response, ContractError> {
+    if !(MIN_INACTIVITY_PERIOD..=MAX_INACTIVITY_PERIOD).contains(&inactivity_period) {
+        return Err(ContractError::InvalidInactivityPeriod {
+            min: MIN_INACTIVITY_PERIOD,
+            max: MAX_INACTIVITY_PERIOD,
+        });
+    }
+
+    let beneficiary = Beneficiary {
+        address: deps.api.addr_validate(&beneficiary)?,
+        inactivity_period,
+    };
+
+    match locker_id {
+        Some(locker_id) => {
+            let locker = LOCKERS
+                .may_load(deps.storage, locker_id)?
+                .ok_or(ContractError::LockerNotFound {})?;
+
+            if locker.owner != info.sender {
+                return Err(ContractError::NotOwner {});
+            }
+            LOCKER_BENEFICIARIES.save(deps.storage, locker_id, &beneficiary)?;
+        }
+        None => OWNER_BENEFICIARIES.save(deps.storage, &info.sender, &beneficiary)?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_beneficiary")
+        .add_attribute("owner", info.sender)
+        .add_attribute("locker_id", locker_id.map_or(String::new(), |id| id.to_string()))
+        .add_attribute("beneficiary", beneficiary.address)
+        .add_attribute("inactivity_period", inactivity_period.to_string()))
+}
+
+fn execute_remove_beneficiary(
+    deps: DepsMut,
+    info: MessageInfo,
+    locker_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    match locker_id {
+        Some(locker_id) => {
+            let locker = LOCKERS
+                .may_load(deps.storage, locker_id)?
+                .ok_or(ContractError::LockerNotFound {})?;
+
+            if locker.owner != info.sender {
+                return Err(ContractError::NotOwner {});
+            }
+            LOCKER_BENEFICIARIES.remove(deps.storage, locker_id);
+        }
+        None => OWNER_BENEFICIARIES.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_beneficiary")
+        .add_attribute("owner", info.sender)
+        .add_attribute("locker_id", locker_id.map_or(String::new(), |id| id.to_string())))
+}
+
+fn execute_claim_inheritance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let mut locker = LOCKERS
+        .may_load(deps.storage, locker_id)?
+        .ok_or(ContractError::LockerNotFound {})?;
+
+    let beneficiary = load_beneficiary(deps.storage, &locker)?
+        .ok_or(ContractError::NoBeneficiary {})?;
+
+    if info.sender != beneficiary.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    ensure_not_blocked(deps.storage, &locker.owner)?;
+    ensure_not_blocked(deps.storage, &beneficiary.address)?;
+
+    let claimable_at = inheritance_claimable_at(deps.storage, &locker, &beneficiary)?;
+    if env.block.time.seconds() < claimable_at {
+        return Err(ContractError::OwnerStillActive(claimable_at));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &locker.lp_token)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+    ensure_locker_slot(
+        deps.storage,
+        &whitelist.limits,
+        &config.limits,
+        &beneficiary.address,
+        &locker.lp_token,
+    )?;
+
+    // The locker keeps its terms, so auto-relock lockers pass on too. Receipts
+    // are minted again: the inactive owner's can no longer be handed back.
+    let previous_owner = locker.owner.clone();
+    let kind = LockerEventKind::Inherited {
+        from: previous_owner.clone(),
+        to: beneficiary.address.clone(),
+    };
+    move_locker(deps.storage, &env, &mut locker, &beneficiary.address, &whitelist, kind)?;
+    let mint_msg = mint_receipt_msg(&whitelist, &beneficiary.address, locker.amount)?;
+    let unstake_msg = reward_controller_msg(
+        deps.storage,
+        &RewardControllerExecuteMsg::LockerTransferred { locker_id },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(mint_msg)
+        .add_messages(unstake_msg)
+        .add_event(Event::from(events::Inherited {
+            locker_id,
+            lp_token: locker.lp_token,
+            amount: locker.amount,
+            from: previous_owner.clone(),
+            to: beneficiary.address.clone(),
+        }))
+        .add_attribute("action", "claim_inheritance")
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("owner", previous_owner)
+        .add_attribute("beneficiary", beneficiary.address))
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: Option<String>,
+    reward_controller: Option<String>,
+    emergency_unlock_delay: Option<u64>,
+    platform_fee_bps: Option<u16>,
+    keeper_tip_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(admin_addr) = admin {
         config.admin = deps.api.addr_validate(&admin_addr)?;
     }
 
-    if let Some(reward_addr) = reward_controller {
-        config.reward_controller = Some(deps.api.addr_validate(&reward_addr)?);
+    if let Some(reward_addr) = reward_controller {
+        config.reward_controller = Some(deps.api.addr_validate(&reward_addr)?);
+    }
+
+    if let Some(delay) = emergency_unlock_delay {
+        config.emergency_unlock_delay = delay;
+    }
+
+    if let Some(fee) = platform_fee_bps {
+        config.platform_fee_bps = fee;
+    }
+
+    if let Some(tip) = keeper_tip_bps {
+        config.keeper_tip_bps = tip;
+    }
+
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_whitelist_lp(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: String,
+    min_lock_duration: u64,
+    max_lock_duration: u64,
+    bonus_multiplier: Decimal,
+    tiers: Option<Vec<LockTier>>,
+    pair_contract: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let pair = pair_contract
+        .map(|pair_contract| {
+            let pair_addr = deps.api.addr_validate(&pair_contract)?;
+            verify_lp_pair(deps.as_ref(), &lp_addr, &pair_addr)
+        })
+        .transpose()?;
+
+    let params = LPDefaults {
+        min_lock_duration,
+        max_lock_duration,
+        bonus_multiplier,
+        tiers: tiers.unwrap_or_default(),
+    };
+    add_whitelisted_lp(deps.storage, &config, &lp_addr, params, pair)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "whitelist_lp")
+        .add_attribute("lp_token", lp_token))
+}
+
+fn execute_whitelist_lp_from_factory(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_contract: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pair_addr = deps.api.addr_validate(&pair_contract)?;
+
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&pair_addr, &PairQueryMsg::Pair {})
+        .map_err(|_| ContractError::UnverifiedLP {})?;
+    let lp_token = pair_info.liquidity_token;
+    let pair = verify_lp_pair(deps.as_ref(), &lp_token, &pair_addr)?;
+
+    let params = TRUSTED_FACTORIES
+        .load(deps.storage, &pair.factory)?
+        .permissionless
+        .ok_or(ContractError::PermissionlessDisabled {})?;
+    add_whitelisted_lp(deps.storage, &config, &lp_token, params, Some(pair))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "whitelist_lp_from_factory")
+        .add_attribute("lp_token", lp_token)
+        .add_attribute("pair_contract", pair_addr)
+        .add_attribute("sender", info.sender))
+}
+
+fn add_whitelisted_lp(
+    storage: &mut dyn Storage,
+    config: &Config,
+    lp_token: &Addr,
+    params: LPDefaults,
+    pair: Option<LPPair>,
+) -> Result<(), ContractError> {
+    // Existing entries are changed through UpdateLP so their state is never reset
+    if WHITELISTED_LPS.has(storage, lp_token) {
+        return Err(ContractError::LPAlreadyWhitelisted {});
+    }
+
+    let whitelist = WhitelistedLP {
+        lp_token: lp_token.clone(),
+        min_lock_duration: params.min_lock_duration,
+        max_lock_duration: params.max_lock_duration,
+        enabled: true,
+        bonus_multiplier: params.bonus_multiplier,
+        tiers: params.tiers,
+        paused: false,
+        receipt_token: None,
+        pair,
+        limits: LockLimits::default(),
+    };
+    validate_whitelisted_lp(&config.bounds, &whitelist)?;
+
+    WHITELISTED_LPS.save(storage, lp_token, &whitelist)?;
+    Ok(())
+}
+
+/// Proves `lp_token` is the liquidity token of a pair registered in a trusted factory
+fn verify_lp_pair(
+    deps: Deps,
+    lp_token: &Addr,
+    pair_contract: &Addr,
+) -> Result<LPPair, ContractError> {
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(pair_contract, &PairQueryMsg::Pair {})
+        .map_err(|_| ContractError::UnverifiedLP {})?;
+
+    if pair_info.contract_addr != *pair_contract || pair_info.liquidity_token != *lp_token {
+        return Err(ContractError::UnverifiedLP {});
+    }
+
+    let factories: Vec<Addr> = TRUSTED_FACTORIES
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    // A pair could claim any liquidity token; only the factory's registry is trusted
+    for factory in factories {
+        let registered: StdResult<PairInfo> = deps.querier.query_wasm_smart(
+            &factory,
+            &FactoryQueryMsg::Pair {
+                asset_infos: pair_info.asset_infos.clone(),
+            },
+        );
+
+        if registered.is_ok_and(|registered| {
+            registered.contract_addr == *pair_contract && registered.liquidity_token == *lp_token
+        }) {
+            return Ok(LPPair {
+                factory,
+                pair_contract: pair_contract.clone(),
+                asset_infos: pair_info.asset_infos,
+            });
+        }
+    }
+
+    Err(ContractError::UnverifiedLP {})
+}
+
+fn execute_add_trusted_factory(
+    deps: DepsMut,
+    info: MessageInfo,
+    factory: String,
+    permissionless: Option<LPDefaults>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Defaults are checked up front so permissionless whitelisting cannot fail on them
+    if let Some(params) = &permissionless {
+        validate_lock_durations(params.min_lock_duration, params.max_lock_duration)?;
+        validate_bonus_multiplier(&config.bounds, params.bonus_multiplier)?;
+        validate_tiers(
+            &config.bounds,
+            params.max_lock_duration,
+            params.bonus_multiplier,
+            &params.tiers,
+        )?;
+    }
+
+    let factory_addr = deps.api.addr_validate(&factory)?;
+    let permissionless_enabled = permissionless.is_some();
+    TRUSTED_FACTORIES.save(deps.storage, &factory_addr, &TrustedFactory { permissionless })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_trusted_factory")
+        .add_attribute("factory", factory)
+        .add_attribute("permissionless", permissionless_enabled.to_string()))
+}
+
+fn execute_remove_trusted_factory(
+    deps: DepsMut,
+    info: MessageInfo,
+    factory: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let factory_addr = deps.api.addr_validate(&factory)?;
+    TRUSTED_FACTORIES.remove(deps.storage, &factory_addr);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_trusted_factory")
+        .add_attribute("factory", factory))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_update_lp(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: String,
+    min_lock_duration: Option<u64>,
+    max_lock_duration: Option<u64>,
+    bonus_multiplier: Option<Decimal>,
+    tiers: Option<Vec<LockTier>>,
+    enabled: Option<bool>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let mut whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &lp_addr)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    if let Some(min) = min_lock_duration {
+        whitelist.min_lock_duration = min;
+    }
+
+    if let Some(max) = max_lock_duration {
+        whitelist.max_lock_duration = max;
+    }
+
+    if let Some(multiplier) = bonus_multiplier {
+        whitelist.bonus_multiplier = multiplier;
+    }
+
+    if let Some(tiers) = tiers {
+        whitelist.tiers = tiers;
+    }
+
+    if let Some(status) = enabled {
+        whitelist.enabled = status;
+    }
+
+    validate_whitelisted_lp(&config.bounds, &whitelist)?;
+    WHITELISTED_LPS.save(deps.storage, &lp_addr, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_lp")
+        .add_attribute("lp_token", lp_token)
+        .add_attribute("enabled", whitelist.enabled.to_string()))
+}
+
+fn execute_disable_lp(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let mut whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &lp_addr)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    whitelist.enabled = false;
+    WHITELISTED_LPS.save(deps.storage, &lp_addr, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "disable_lp")
+        .add_attribute("lp_token", lp_token))
+}
+
+fn execute_remove_lp(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+
+    // Existing lockers rely on the entry to extend; use DisableLP instead
+    let total = TOTAL_LOCKED.may_load(deps.storage, &lp_addr)?.unwrap_or_default();
+    if !total.is_zero() {
+        return Err(ContractError::LPHasActiveLocks {});
+    }
+
+    WHITELISTED_LPS.remove(deps.storage, &lp_addr);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_lp")
+        .add_attribute("lp_token", lp_token))
+}
+
+fn execute_pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.paused = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "pause"))
+}
+
+fn execute_resume(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.paused = false;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "resume"))
+}
+
+fn execute_set_pause_flags(
+    deps: DepsMut,
+    info: MessageInfo,
+    locks: Option<bool>,
+    extends: Option<bool>,
+    emergency_requests: Option<bool>,
+    transfers: Option<bool>,
+    fee_withdrawal: Option<bool>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(paused) = locks {
+        config.pause.locks = paused;
+    }
+
+    if let Some(paused) = extends {
+        config.pause.extends = paused;
+    }
+
+    if let Some(paused) = emergency_requests {
+        config.pause.emergency_requests = paused;
+    }
+
+    if let Some(paused) = transfers {
+        config.pause.transfers = paused;
+    }
+
+    if let Some(paused) = fee_withdrawal {
+        config.pause.fee_withdrawal = paused;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_pause_flags")
+        .add_attribute("locks", config.pause.locks.to_string())
+        .add_attribute("extends", config.pause.extends.to_string())
+        .add_attribute("emergency_requests", config.pause.emergency_requests.to_string())
+        .add_attribute("transfers", config.pause.transfers.to_string())
+        .add_attribute("fee_withdrawal", config.pause.fee_withdrawal.to_string()))
+}
+
+fn execute_set_lp_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: String,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let mut whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &lp_addr)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    whitelist.paused = paused;
+    WHITELISTED_LPS.save(deps.storage, &lp_addr, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_lp_paused")
+        .add_attribute("lp_token", lp_token)
+        .add_attribute("paused", paused.to_string()))
+}
+
+fn execute_set_lock_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: Option<String>,
+    limits: LockLimits,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let scope = match lp_token {
+        Some(lp_token) => {
+            let lp_addr = deps.api.addr_validate(&lp_token)?;
+            let mut whitelist = WHITELISTED_LPS
+                .may_load(deps.storage, &lp_addr)?
+                .ok_or(ContractError::LPNotWhitelisted {})?;
+
+            whitelist.limits = limits;
+            WHITELISTED_LPS.save(deps.storage, &lp_addr, &whitelist)?;
+            lp_token
+        }
+        None => {
+            config.limits = limits;
+            CONFIG.save(deps.storage, &config)?;
+            "default".to_string()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "set_lock_limits")
+        .add_attribute("lp_token", scope))
+}
+
+fn execute_update_blocklist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in &add {
+        BLOCKLIST.save(deps.storage, &deps.api.addr_validate(address)?, &true)?;
+    }
+    for address in &remove {
+        BLOCKLIST.remove(deps.storage, &deps.api.addr_validate(address)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_blocklist")
+        .add_attribute("added", add.join(","))
+        .add_attribute("removed", remove.join(",")))
+}
+
+fn execute_set_screening(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: Option<String>,
+    recovery_address: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.screening = Screening {
+        contract: contract.map(|addr| deps.api.addr_validate(&addr)).transpose()?,
+        recovery_address: recovery_address
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "set_screening"))
+}
+
+fn execute_recover_blocked_lockers(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_batch(&locker_ids)?;
+    let recovery = config
+        .screening
+        .recovery_address
+        .ok_or(ContractError::NoRecoveryAddress {})?;
+
+    // Lock terms still apply: only matured lockers are recovered
+    let mut totals: BTreeMap<Addr, Uint128> = BTreeMap::new();
+    let mut released = Vec::with_capacity(locker_ids.len());
+    for locker_id in &locker_ids {
+        let locker = LOCKERS
+            .may_load(deps.storage, *locker_id)?
+            .ok_or_else(|| batch_error(*locker_id, ContractError::LockerNotFound {}))?;
+
+        if !BLOCKLIST.has(deps.storage, &locker.owner) {
+            return Err(batch_error(*locker_id, ContractError::NotBlocked {}));
+        }
+
+        // A blocked owner can never return the receipts, so they are written off
+        let locker = release_matured_locker(deps.branch(), &env, locker, true)
+            .map_err(|err| batch_error(*locker_id, err))?;
+        released.push(Event::from(events::Unlocked {
+            recipient: recovery.clone(),
+            ..unlocked(&locker, UnlockKind::Recovered)
+        }));
+        let total = totals.entry(locker.lp_token).or_default();
+        *total = total.checked_add(locker.amount)?;
+    }
+
+    let mut messages = Vec::with_capacity(totals.len());
+    for (lp_token, amount) in totals {
+        messages.push(cw20_transfer_msg(&lp_token, &recovery, amount)?);
+    }
+    messages.extend(lockers_closed_msg(deps.storage, locker_ids.clone())?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_events(released)
+        .add_attribute("action", "recover_blocked_lockers")
+        .add_attribute("recipient", recovery)
+        .add_attribute("locker_ids", join_ids(&locker_ids)))
+}
+
+fn execute_set_receipt_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    receipt_token: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let mut whitelist = WHITELISTED_LPS
+        .may_load(deps.storage, &lp_addr)?
+        .ok_or(ContractError::LPNotWhitelisted {})?;
+
+    // Every locker of an LP must share the same receipt rules
+    let total = TOTAL_LOCKED.may_load(deps.storage, &lp_addr)?.unwrap_or_default();
+    if !total.is_zero() {
+        return Err(ContractError::LPHasActiveLocks {});
+    }
+
+    whitelist.receipt_token = match receipt_token {
+        Some(addr) => {
+            let receipt_addr = deps.api.addr_validate(&addr)?;
+            let minter: Option<MinterResponse> = deps
+                .querier
+                .query_wasm_smart(&receipt_addr, &Cw20QueryMsg::Minter {})?;
+            if minter.map(|m| m.minter) != Some(env.contract.address.to_string()) {
+                return Err(ContractError::ReceiptMinterMismatch {});
+            }
+            Some(receipt_addr)
+        }
+        None => None,
+    };
+
+    WHITELISTED_LPS.save(deps.storage, &lp_addr, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_receipt_token")
+        .add_attribute("lp_token", lp_token)
+        .add_attribute(
+            "receipt_token",
+            whitelist
+                .receipt_token
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+        ))
+}
+
+fn execute_recover_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    ensure_not_paused(&config, config.pause.fee_withdrawal)?;
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    // Only the balance above what lockers hold can ever leave through this path
+    let available = match &asset {
+        AssetInfo::Cw20(token) => lp_surplus(deps.as_ref(), &env, token)?,
+        AssetInfo::Native(denom) => {
+            deps.querier.query_balance(&env.contract.address, denom)?.amount
+        }
+    };
+
+    if amount > available {
+        return Err(ContractError::InsufficientSurplus { available });
+    }
+
+    let recover_msg: CosmosMsg = match &asset {
+        AssetInfo::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        AssetInfo::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+    };
+
+    Ok(Response::new()
+        .add_message(recover_msg)
+        .add_attribute("action", "recover_tokens")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::ForcePause {} => sudo_force_pause(deps),
+        SudoMsg::UpdateAdmin { admin } => sudo_update_admin(deps, admin),
+        SudoMsg::ForceRelease { locker_ids } => sudo_force_release(deps, env, locker_ids),
+        SudoMsg::UpdateBounds { bounds } => sudo_update_bounds(deps, bounds),
+    }
+}
+
+fn sudo_force_pause(deps: DepsMut) -> Result<Response, ContractError> {
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.paused = true;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "sudo_force_pause"))
+}
+
+fn sudo_update_admin(deps: DepsMut, admin: String) -> Result<Response, ContractError> {
+    let admin = deps.api.addr_validate(&admin)?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.admin = admin.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_update_admin")
+        .add_attribute("admin", admin))
+}
+
+fn sudo_update_bounds(deps: DepsMut, bounds: ConfigBounds) -> Result<Response, ContractError> {
+    let within_limits = bounds.min_emergency_unlock_delay >= MIN_EMERGENCY_UNLOCK_DELAY
+        && bounds.max_emergency_unlock_delay <= MAX_EMERGENCY_UNLOCK_DELAY
+        && bounds.min_emergency_unlock_delay <= bounds.max_emergency_unlock_delay
+        && bounds.max_platform_fee_bps <= MAX_PLATFORM_FEE_BPS
+        && bounds.max_bonus_multiplier >= Decimal::one()
+        && bounds.max_bonus_multiplier <= MAX_BONUS_MULTIPLIER;
+    if !within_limits {
+        return Err(ContractError::InvalidBounds {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.bounds = bounds;
+    validate_config(&config).map_err(|_| ContractError::InvalidBounds {})?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "sudo_update_bounds"))
+}
+
+fn sudo_force_release(
+    mut deps: DepsMut,
+    env: Env,
+    locker_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    validate_batch(&locker_ids)?;
+
+    let mut messages = Vec::with_capacity(locker_ids.len());
+    let mut unlocked = Vec::with_capacity(locker_ids.len());
+    for locker_id in &locker_ids {
+        let locker = LOCKERS
+            .may_load(deps.storage, *locker_id)?
+            .ok_or_else(|| batch_error(*locker_id, ContractError::LockerNotFound {}))?;
+
+        // Receipts would stay in circulation without any LP behind them
+        ensure_receipt_returned(deps.as_ref(), &locker, false)
+            .map_err(|err| batch_error(*locker_id, err))?;
+
+        history::record(
+            deps.storage,
+            &env,
+            *locker_id,
+            LockerEventKind::ForceReleased {
+                amount: locker.amount,
+            },
+        )?;
+        remove_locker(deps.branch(), &env, &locker)?;
+        messages.push(transfer_lp_msg(&locker)?);
+        unlocked.push(unlocked_event(&locker, UnlockKind::Forced));
+    }
+
+    messages.extend(lockers_closed_msg(deps.storage, locker_ids.clone())?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_events(unlocked)
+        .add_attribute("action", "sudo_force_release")
+        .add_attribute("locker_ids", join_ids(&locker_ids)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::IsBlocked { address } => to_json_binary(&query_is_blocked(deps, address)?),
+        QueryMsg::Locker { locker_id } => to_json_binary(&query_locker(deps, env, locker_id)?),
+        QueryMsg::LockersByOwner { owner, start_after, limit } => {
+            to_json_binary(&query_lockers_by_owner(deps, env, owner, start_after, limit)?)
+        }
+        QueryMsg::WhitelistedLP { lp_token } => {
+            to_json_binary(&query_whitelisted_lp(deps, lp_token)?)
+        }
+        QueryMsg::TierFor { lp_token, duration } => {
+            to_json_binary(&query_tier_for(deps, lp_token, duration)?)
+        }
+        QueryMsg::AllWhitelistedLPs { start_after, limit } => {
+            to_json_binary(&query_all_whitelisted_lps(deps, start_after, limit)?)
+        }
+        QueryMsg::LockerValue { locker_id } => {
+            to_json_binary(&query_locker_value(deps, locker_id)?)
+        }
+        QueryMsg::LockedValueByLP { lp_token } => {
+            to_json_binary(&query_locked_value_by_lp(deps, lp_token)?)
+        }
+        QueryMsg::UnlockableLockers { owner, start_after, limit } => {
+            to_json_binary(&query_unlockable_lockers(deps, env, owner, start_after, limit)?)
+        }
+        QueryMsg::OwnerPortfolio { owner, start_after, limit } => {
+            to_json_binary(&query_owner_portfolio(deps, env, owner, start_after, limit)?)
+        }
+        QueryMsg::LockerHistory { locker_id, start_after, limit } => {
+            to_json_binary(&query_locker_history(deps, locker_id, start_after, limit)?)
+        }
+        QueryMsg::TrustedFactories {} => to_json_binary(&query_trusted_factories(deps)?),
+        QueryMsg::TotalLockedByLP { lp_token } => {
+            to_json_binary(&query_total_locked(deps, lp_token)?)
+        }
+        QueryMsg::LockedBalanceAt { owner, lp_token, height } => {
+            to_json_binary(&query_locked_balance_at(deps, owner, lp_token, height)?)
+        }
+        QueryMsg::TotalLockedAt { lp_token, height } => {
+            to_json_binary(&query_total_locked_at(deps, lp_token, height)?)
+        }
+        QueryMsg::VotingPower { address, at_time } => {
+            to_json_binary(&query_voting_power(deps, env, address, at_time)?)
+        }
+        QueryMsg::TotalVotingPower { at_time } => {
+            to_json_binary(&query_total_voting_power(deps, env, at_time)?)
+        }
+        QueryMsg::Operator { owner, operator } => {
+            to_json_binary(&query_operator(deps, owner, operator)?)
+        }
+        QueryMsg::Beneficiary { locker_id } => {
+            to_json_binary(&query_beneficiary(deps, locker_id)?)
+        }
+        QueryMsg::MigrationHistory {} => to_json_binary(&query_migration_history(deps)?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: config.admin,
+        reward_controller: config.reward_controller,
+        emergency_unlock_delay: config.emergency_unlock_delay,
+        platform_fee_bps: config.platform_fee_bps,
+        keeper_tip_bps: config.keeper_tip_bps,
+        paused: config.paused,
+        pause: config.pause,
+        next_locker_id: config.next_locker_id,
+        bounds: config.bounds,
+        limits: config.limits,
+        screening: config.screening,
+    })
+}
+
+fn query_locker(deps: Deps, env: Env, locker_id: u64) -> StdResult<LockerResponse> {
+    let locker = LOCKERS.load(deps.storage, locker_id)?;
+    Ok(locker_response(locker, env.block.time.seconds()))
+}
+
+fn query_lockers_by_owner(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LockersResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let current_time = env.block.time.seconds();
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let lockers: Vec<LockerResponse> = USER_LOCKERS
+        .prefix(&owner_addr)
+        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+        .take(limit)
+        .filter_map(|item| {
+            item.ok().and_then(|(locker_id, _)| {
+                LOCKERS
+                    .load(deps.storage, locker_id)
+                    .ok()
+                    .map(|locker| locker_response(locker, current_time))
+            })
+        })
+        .collect();
+
+    Ok(LockersResponse { lockers })
+}
+
+fn query_unlockable_lockers(
+    deps: Deps,
+    env: Env,
+    owner: Option<String>,
+    start_after: Option<(u64, u64)>,
+    limit: Option<u32>,
+) -> StdResult<UnlockableLockersResponse> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let now = env.block.time.seconds();
+    let start = start_after.map(Bound::exclusive);
+    let end = Some(Bound::inclusive((now, u64::MAX)));
+
+    let (lockers, start_after) = match owner {
+        Some(owner) => {
+            let owner = deps.api.addr_validate(&owner)?;
+            let keys: Vec<(u64, u64)> = OWNER_MATURITY_INDEX
+                .sub_prefix(&owner)
+                .keys(deps.storage, start, end, cosmwasm_std::Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<_>>()?;
+
+            let lockers = keys
+                .iter()
+                .map(|(_, locker_id)| LOCKERS.load(deps.storage, *locker_id))
+                .collect::<StdResult<Vec<_>>>()?;
+            let next = keys.last().copied().filter(|_| keys.len() == limit);
+            (lockers, next)
+        }
+        None => {
+            let keys = MATURITY_INDEX
+                .keys(deps.storage, start, end, cosmwasm_std::Order::Ascending)
+                .take(MAX_UNLOCKABLE_SCAN);
+
+            // Skip what `ReleaseMatured` would reject; the cap bounds the skipping
+            let mut releasable = vec![];
+            let mut scanned = 0;
+            let mut last = None;
+            for key in keys {
+                let key = key?;
+                scanned += 1;
+                last = Some(key);
+                let locker = LOCKERS.load(deps.storage, key.1)?;
+
+                if KEEPER_OPT_OUTS.has(deps.storage, &locker.owner)
+                    || load_receipt_token(deps, &locker.lp_token)?.is_some()
+                {
+                    continue;
+                }
+
+                releasable.push(locker);
+                if releasable.len() == limit {
+                    break;
+                }
+            }
+            let next = last.filter(|_| releasable.len() == limit || scanned == MAX_UNLOCKABLE_SCAN);
+            (releasable, next)
+        }
+    };
+
+    Ok(UnlockableLockersResponse {
+        lockers: lockers
+            .into_iter()
+            .map(|locker| locker_response(locker, now))
+            .collect(),
+        start_after,
+    })
+}
+
+fn query_owner_portfolio(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OwnerPortfolioResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let now = env.block.time.seconds();
+    let order = cosmwasm_std::Order::Ascending;
+    let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let totals: Vec<(Addr, OwnerTotals)> = OWNER_TOTALS
+        .prefix(&owner)
+        .range(deps.storage, start, None, order)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    // Maturity depends on the block time, so it is read from the index. All
+    // entries of a page share one scan budget for their matured lockers.
+    let mut budget = MAX_UNLOCKABLE_SCAN;
+    let mut entries = Vec::with_capacity(totals.len());
+    for (lp_token, totals) in totals {
+        if budget == 0 {
+            break;
+        }
+
+        let maturities = OWNER_LP_MATURITY_INDEX.prefix((&owner, &lp_token));
+        let matured = Some(Bound::inclusive((now, u64::MAX)));
+        let keys: Vec<(u64, u64)> = maturities
+            .keys(deps.storage, None, matured, order)
+            .take(budget + 1)
+            .collect::<StdResult<_>>()?;
+        let unlockable_capped = keys.len() > budget;
+
+        let mut unlockable = Uint128::zero();
+        for (_, locker_id) in keys.into_iter().take(budget) {
+            unlockable = unlockable.checked_add(LOCKERS.load(deps.storage, locker_id)?.amount)?;
+            budget -= 1;
+        }
+
+        let locked = Some(Bound::exclusive((now, u64::MAX)));
+        let next_unlock_time = maturities
+            .keys(deps.storage, locked, None, order)
+            .next()
+            .transpose()?
+            .map(|(unlock_time, _)| unlock_time);
+
+        entries.push(PortfolioEntry {
+            amount: LOCKED_BALANCES
+                .may_load(deps.storage, (&owner, &lp_token))?
+                .unwrap_or_default(),
+            lp_token,
+            lockers: totals.lockers,
+            next_unlock_time,
+            unlockable,
+            unlockable_capped,
+            emergency_pending: totals.emergency_pending,
+        });
+    }
+
+    let start_after = entries
+        .last()
+        .filter(|_| entries.len() == limit || budget == 0)
+        .map(|entry| entry.lp_token.clone());
+
+    // Totals across every LP token, not only this page
+    let lockers = OWNER_LOCKER_COUNT.may_load(deps.storage, &owner)?.unwrap_or_default();
+    let locked = Some(Bound::exclusive((now, u64::MAX)));
+    let next_unlock_time = OWNER_MATURITY_INDEX
+        .sub_prefix(&owner)
+        .keys(deps.storage, locked, None, order)
+        .next()
+        .transpose()?
+        .map(|(unlock_time, _)| unlock_time);
+
+    Ok(OwnerPortfolioResponse {
+        owner,
+        lockers,
+        next_unlock_time,
+        entries,
+        start_after,
+    })
+}
+
+fn query_whitelisted_lp(deps: Deps, lp_token: String) -> StdResult<WhitelistedLPResponse> {
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let whitelist = WHITELISTED_LPS.load(deps.storage, &lp_addr)?;
+    Ok(whitelisted_lp_response(whitelist))
+}
+
+fn query_tier_for(deps: Deps, lp_token: String, duration: u64) -> StdResult<TierForResponse> {
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let whitelist = WHITELISTED_LPS.load(deps.storage, &lp_addr)?;
+    Ok(TierForResponse {
+        duration,
+        multiplier: tier_multiplier(&whitelist, duration),
+    })
+}
+
+fn query_all_whitelisted_lps(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<WhitelistedLPResponse>> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    WHITELISTED_LPS
+        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, whitelist)| whitelisted_lp_response(whitelist)))
+        .collect()
+}
+
+fn query_trusted_factories(deps: Deps) -> StdResult<Vec<TrustedFactoryResponse>> {
+    TRUSTED_FACTORIES
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| {
+            let (factory, trusted) = item?;
+            Ok(TrustedFactoryResponse {
+                factory,
+                permissionless: trusted.permissionless,
+            })
+        })
+        .collect()
+}
+
+fn query_total_locked(deps: Deps, lp_token: String) -> StdResult<TotalLockedResponse> {
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let total = TOTAL_LOCKED.may_load(deps.storage, &lp_addr)?.unwrap_or_default();
+    
+    Ok(TotalLockedResponse {
+        lp_token: lp_addr,
+        total_amount: total,
+    })
+}
+
+fn query_locker_history(
+    deps: Deps,
+    locker_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LockerHistoryResponse> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    Ok(LockerHistoryResponse {
+        events: history::events(deps.storage, locker_id, start_after, limit)?,
+    })
+}
+
+fn query_locker_value(deps: Deps, locker_id: u64) -> StdResult<LockedValueResponse> {
+    let locker = LOCKERS.load(deps.storage, locker_id)?;
+    let assets = underlying_assets(deps, &locker.lp_token, locker.amount)?;
+
+    Ok(LockedValueResponse {
+        lp_token: locker.lp_token,
+        amount: locker.amount,
+        assets,
+    })
+}
+
+fn query_locked_value_by_lp(deps: Deps, lp_token: String) -> StdResult<LockedValueResponse> {
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let total = TOTAL_LOCKED.may_load(deps.storage, &lp_addr)?.unwrap_or_default();
+    let assets = underlying_assets(deps, &lp_addr, total)?;
+
+    Ok(LockedValueResponse {
+        lp_token: lp_addr,
+        amount: total,
+        assets,
+    })
+}
+
+/// Values `amount` of LP at the reserves of the pair recorded on its whitelist entry
+fn underlying_assets(deps: Deps, lp_token: &Addr, amount: Uint128) -> StdResult<Vec<Asset>> {
+    let pair = WHITELISTED_LPS
+        .may_load(deps.storage, lp_token)?
+        .and_then(|whitelist| whitelist.pair)
+        .ok_or_else(|| StdError::generic_err("LP token has no verified pair"))?;
+
+    let pool: PoolResponse = deps
+        .querier
+        .query_wasm_smart(&pair.pair_contract, &PairQueryMsg::Pool {})?;
+    let token_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(lp_token, &Cw20QueryMsg::TokenInfo {})?;
+
+    Ok(pool
+        .assets
+        .into_iter()
+        .map(|asset| Asset {
+            amount: if token_info.total_supply.is_zero() {
+                Uint128::zero()
+            } else {
+                asset.amount.multiply_ratio(amount, token_info.total_supply)
+            },
+            info: asset.info,
+        })
+        .collect())
+}
+
+fn query_locked_balance_at(
+    deps: Deps,
+    owner: String,
+    lp_token: String,
+    height: u64,
+) -> StdResult<LockedBalanceResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let amount = LOCKED_BALANCES
+        .may_load_at_height(deps.storage, (&owner_addr, &lp_addr), height)?
+        .unwrap_or_default();
+
+    Ok(LockedBalanceResponse {
+        owner: owner_addr,
+        lp_token: lp_addr,
+        amount,
+        height,
+    })
+}
+
+fn query_total_locked_at(
+    deps: Deps,
+    lp_token: String,
+    height: u64,
+) -> StdResult<TotalLockedResponse> {
+    let lp_addr = deps.api.addr_validate(&lp_token)?;
+    let total = TOTAL_LOCKED
+        .may_load_at_height(deps.storage, &lp_addr, height)?
+        .unwrap_or_default();
+
+    Ok(TotalLockedResponse {
+        lp_token: lp_addr,
+        total_amount: total,
+    })
+}
+
+fn query_voting_power(
+    deps: Deps,
+    env: Env,
+    address: String,
+    at_time: Option<u64>,
+) -> StdResult<VotingPowerResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let at_time = at_time.unwrap_or_else(|| env.block.time.seconds());
+
+    Ok(VotingPowerResponse {
+        power: ve::voting_power(deps.storage, Some(&addr), at_time)?,
+        at_time,
+    })
+}
+
+fn query_total_voting_power(
+    deps: Deps,
+    env: Env,
+    at_time: Option<u64>,
+) -> StdResult<VotingPowerResponse> {
+    let at_time = at_time.unwrap_or_else(|| env.block.time.seconds());
+
+    Ok(VotingPowerResponse {
+        power: ve::voting_power(deps.storage, None, at_time)?,
+        at_time,
+    })
+}
+
+fn query_operator(deps: Deps, owner: String, operator: String) -> StdResult<OperatorResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let operator = deps.api.addr_validate(&operator)?;
+    let approval = OPERATORS.may_load(deps.storage, (&owner, &operator))?;
+
+    Ok(OperatorResponse {
+        owner,
+        operator,
+        permissions: approval.as_ref().map(|a| a.permissions.clone()).unwrap_or_default(),
+        expires: approval.as_ref().map(|a| a.expires).unwrap_or_default(),
+        transfer_recipients: approval.map(|a| a.transfer_recipients).unwrap_or_default(),
+    })
+}
+
+fn query_beneficiary(deps: Deps, locker_id: u64) -> StdResult<BeneficiaryResponse> {
+    let locker = LOCKERS.load(deps.storage, locker_id)?;
+    let beneficiary = load_beneficiary(deps.storage, &locker)?;
+    let claimable_at = beneficiary
+        .as_ref()
+        .map(|beneficiary| inheritance_claimable_at(deps.storage, &locker, beneficiary))
+        .transpose()?;
+
+    Ok(BeneficiaryResponse {
+        beneficiary,
+        last_activity: LAST_ACTIVITY.may_load(deps.storage, &locker.owner)?,
+        claimable_at,
+    })
+}
+
+fn query_is_blocked(deps: Deps, address: String) -> StdResult<ScreeningResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(ScreeningResponse {
+        blocked: BLOCKLIST.has(deps.storage, &address),
+    })
+}
+
+fn query_migration_history(deps: Deps) -> StdResult<Vec<MigrationRecord>> {
+    MIGRATION_HISTORY
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    mut deps: DepsMut,
+    env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    let version = cw2::get_contract_version(deps.storage)?;
+    
+    if version.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {});
+    }
+
+    let from = Version::parse(&version.version).map_err(|_| ContractError::InvalidMigration {})?;
+    let to = Version::parse(CONTRACT_VERSION).map_err(|_| ContractError::InvalidMigration {})?;
+
+    if from > to {
+        return Err(ContractError::MigrationDowngrade {
+            from: version.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    let steps = migrations::run(deps.branch(), &env, &from, &to)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(addr) = msg.reward_controller {
+        config.reward_controller = Some(deps.api.addr_validate(&addr)?);
+    }
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Several migrations can run in one block, so records are numbered instead
+    let sequence = MIGRATION_HISTORY
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    MIGRATION_HISTORY.save(
+        deps.storage,
+        sequence,
+        &MigrationRecord {
+            from_version: version.version.clone(),
+            to_version: CONTRACT_VERSION.to_string(),
+            steps: steps.clone(),
+            height: env.block.height,
+            time: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", version.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("steps", steps.join(",")))
+}
+
+// Helper functions
+/// Adds to the snapshotted owner balance and LP total; every locker mutation goes through here
+fn increase_locked(
+    storage: &mut dyn Storage,
+    height: u64,
+    owner: &Addr,
+    lp_token: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    LOCKED_BALANCES.update(storage, (owner, lp_token), height, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+    TOTAL_LOCKED.update(storage, lp_token, height, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+fn decrease_locked(
+    storage: &mut dyn Storage,
+    height: u64,
+    owner: &Addr,
+    lp_token: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    LOCKED_BALANCES.update(storage, (owner, lp_token), height, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    TOTAL_LOCKED.update(storage, lp_token, height, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    Ok(())
+}
+
+fn ensure_within_lock_cap(
+    storage: &dyn Storage,
+    limits: &LockLimits,
+    lp_token: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if let Some(cap) = limits.max_total_locked {
+        let total = TOTAL_LOCKED.may_load(storage, lp_token)?.unwrap_or_default();
+        if total.checked_add(amount)? > cap {
+            return Err(ContractError::LockCapExceeded { cap });
+        }
+    }
+    Ok(())
+}
+
+/// Checks `owner` can hold one more locker of `lp_token`
+/// The default limit counts the owner's lockers of every LP, an LP override
+/// only those of that LP
+fn ensure_locker_slot(
+    storage: &dyn Storage,
+    lp_limits: &LockLimits,
+    default_limits: &LockLimits,
+    owner: &Addr,
+    lp_token: &Addr,
+) -> Result<(), ContractError> {
+    if let Some(max) = lp_limits.max_lockers_per_owner {
+        let lockers = OWNER_TOTALS
+            .may_load(storage, (owner, lp_token))?
+            .map_or(0, |totals| totals.lockers);
+        if lockers >= max {
+            return Err(ContractError::TooManyLockers { max });
+        }
     }
+    if let Some(max) = default_limits.max_lockers_per_owner {
+        let lockers = OWNER_LOCKER_COUNT.may_load(storage, owner)?.unwrap_or_default();
+        if lockers >= max {
+            return Err(ContractError::TooManyOwnerLockers { max });
+        }
+    }
+    Ok(())
+}
 
-    if let Some(delay) = emergency_unlock_delay {
-        config.emergency_unlock_delay = delay;
+/// Only owners are tracked; callers without lockers have no beneficiaries
+fn record_activity(storage: &mut dyn Storage, env: &Env, caller: &Addr) -> StdResult<()> {
+    let is_owner = USER_LOCKERS
+        .prefix(caller)
+        .keys(storage, None, None, cosmwasm_std::Order::Ascending)
+        .next()
+        .is_some();
+    if is_owner {
+        LAST_ACTIVITY.save(storage, caller, &env.block.time.seconds())?;
     }
+    Ok(())
+}
 
-    if let Some(fee) = platform_fee_bps {
-        config.platform_fee_bps = fee;
+fn load_beneficiary(storage: &dyn Storage, locker: &Locker) -> StdResult<Option<Beneficiary>> {
+    match LOCKER_BENEFICIARIES.may_load(storage, locker.id)? {
+        Some(beneficiary) => Ok(Some(beneficiary)),
+        None => OWNER_BENEFICIARIES.may_load(storage, &locker.owner),
     }
+}
 
-    CONFIG.save(deps.storage, &config)?;
+/// The inactivity period counts from the unlock time or the owner's last
+/// call, whichever is later
+fn inheritance_claimable_at(
+    storage: &dyn Storage,
+    locker: &Locker,
+    beneficiary: &Beneficiary,
+) -> StdResult<u64> {
+    let last_activity = LAST_ACTIVITY.may_load(storage, &locker.owner)?.unwrap_or_default();
+    Ok(locker
+        .unlock_time
+        .max(last_activity)
+        .saturating_add(beneficiary.inactivity_period))
+}
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+/// Blocked owners cannot move their LP out; the admin recovers it instead
+fn ensure_not_blocked(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    if BLOCKLIST.has(storage, address) {
+        return Err(ContractError::Blocked {
+            address: address.to_string(),
+        });
+    }
+    Ok(())
 }
 
-fn execute_whitelist_lp(
-    deps: DepsMut,
-    info: MessageInfo,
-    lp_token: String,
-    min_lock_duration: u64,
-    max_lock_duration: u64,
-    bonus_multiplier: Decimal,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+/// Checks the blocklist, then the screening contract if one is set
+fn ensure_not_screened(deps: Deps, config: &Config, address: &Addr) -> Result<(), ContractError> {
+    ensure_not_blocked(deps.storage, address)?;
+
+    if let Some(contract) = &config.screening.contract {
+        let screening: ScreeningResponse = deps.querier.query_wasm_smart(
+            contract,
+            &ScreeningQueryMsg::IsBlocked {
+                address: address.to_string(),
+            },
+        )?;
+        if screening.blocked {
+            return Err(ContractError::Blocked {
+                address: address.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
 
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+fn index_maturity(storage: &mut dyn Storage, locker: &Locker) -> StdResult<()> {
+    if locker.auto_relock.is_none() {
+        let maturity = (locker.unlock_time, locker.id);
+        MATURITY_INDEX.save(storage, maturity, &true)?;
+        OWNER_MATURITY_INDEX.save(storage, (&locker.owner, locker.unlock_time, locker.id), &true)?;
+        OWNER_LP_MATURITY_INDEX.save(storage, (&locker.owner, &locker.lp_token, maturity), &true)?;
     }
+    Ok(())
+}
 
-    let lp_addr = deps.api.addr_validate(&lp_token)?;
+fn unindex_maturity(storage: &mut dyn Storage, locker: &Locker) {
+    if locker.auto_relock.is_none() {
+        let maturity = (locker.unlock_time, locker.id);
+        MATURITY_INDEX.remove(storage, maturity);
+        OWNER_MATURITY_INDEX.remove(storage, (&locker.owner, locker.unlock_time, locker.id));
+        OWNER_LP_MATURITY_INDEX.remove(storage, (&locker.owner, &locker.lp_token, maturity));
+    }
+}
 
-    let whitelist = WhitelistedLP {
-        lp_token: lp_addr.clone(),
-        min_lock_duration,
-        max_lock_duration,
-        enabled: true,
-        bonus_multiplier,
-    };
+fn add_owner_totals(storage: &mut dyn Storage, locker: &Locker) -> StdResult<()> {
+    OWNER_TOTALS.update(storage, (&locker.owner, &locker.lp_token), |totals| -> StdResult<_> {
+        let mut totals = totals.unwrap_or_default();
+        totals.add(locker)?;
+        Ok(totals)
+    })?;
+    OWNER_LOCKER_COUNT.update(storage, &locker.owner, |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default() + 1)
+    })?;
+    Ok(())
+}
 
-    WHITELISTED_LPS.save(deps.storage, &lp_addr, &whitelist)?;
+fn remove_owner_totals(storage: &mut dyn Storage, locker: &Locker) -> StdResult<()> {
+    let key = (&locker.owner, &locker.lp_token);
+    let mut totals = OWNER_TOTALS.load(storage, key)?;
+    totals.remove(locker)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "whitelist_lp")
-        .add_attribute("lp_token", lp_token))
+    if totals.lockers == 0 {
+        OWNER_TOTALS.remove(storage, key);
+    } else {
+        OWNER_TOTALS.save(storage, key, &totals)?;
+    }
+
+    match OWNER_LOCKER_COUNT.load(storage, &locker.owner)? {
+        1 => OWNER_LOCKER_COUNT.remove(storage, &locker.owner),
+        count => OWNER_LOCKER_COUNT.save(storage, &locker.owner, &(count - 1))?,
+    }
+    Ok(())
 }
 
-fn execute_remove_lp(
-    deps: DepsMut,
-    info: MessageInfo,
-    lp_token: String,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+fn validate_batch(locker_ids: &[u64]) -> Result<(), ContractError> {
+    if locker_ids.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
 
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+    if locker_ids.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge { max: MAX_BATCH_SIZE });
     }
 
-    let lp_addr = deps.api.addr_validate(&lp_token)?;
-    WHITELISTED_LPS.remove(deps.storage, &lp_addr);
+    let mut seen = BTreeSet::new();
+    for locker_id in locker_ids {
+        if !seen.insert(*locker_id) {
+            return Err(ContractError::DuplicateLocker(*locker_id));
+        }
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "remove_lp")
-        .add_attribute("lp_token", lp_token))
+    Ok(())
 }
 
-fn execute_pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+fn batch_error(locker_id: u64, err: ContractError) -> ContractError {
+    ContractError::BatchFailed {
+        locker_id,
+        reason: err.to_string(),
+    }
+}
 
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+/// Fails when the contract is globally paused or the given operation is paused
+fn ensure_not_paused(config: &Config, operation_paused: bool) -> Result<(), ContractError> {
+    if config.paused || operation_paused {
+        return Err(ContractError::Paused {});
     }
+    Ok(())
+}
 
-    config.paused = true;
-    CONFIG.save(deps.storage, &config)?;
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    let bounds = &config.bounds;
+    if config.emergency_unlock_delay < bounds.min_emergency_unlock_delay
+        || config.emergency_unlock_delay > bounds.max_emergency_unlock_delay
+    {
+        return Err(ContractError::InvalidEmergencyUnlockDelay {
+            min: bounds.min_emergency_unlock_delay,
+            max: bounds.max_emergency_unlock_delay,
+        });
+    }
 
-    Ok(Response::new().add_attribute("action", "pause"))
+    if config.platform_fee_bps > bounds.max_platform_fee_bps {
+        return Err(ContractError::PlatformFeeTooHigh {
+            max: bounds.max_platform_fee_bps,
+        });
+    }
+
+    if config.keeper_tip_bps > config.platform_fee_bps {
+        return Err(ContractError::KeeperTipTooHigh {
+            max: config.platform_fee_bps,
+        });
+    }
+    Ok(())
 }
 
-fn execute_resume(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+fn validate_bonus_multiplier(
+    bounds: &ConfigBounds,
+    multiplier: Decimal,
+) -> Result<(), ContractError> {
+    if multiplier < Decimal::one() || multiplier > bounds.max_bonus_multiplier {
+        return Err(ContractError::InvalidBonusMultiplier {
+            max: bounds.max_bonus_multiplier,
+        });
+    }
+    Ok(())
+}
 
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+fn validate_whitelisted_lp(
+    bounds: &ConfigBounds,
+    whitelist: &WhitelistedLP,
+) -> Result<(), ContractError> {
+    validate_lock_durations(whitelist.min_lock_duration, whitelist.max_lock_duration)?;
+    validate_bonus_multiplier(bounds, whitelist.bonus_multiplier)?;
+    validate_tiers(
+        bounds,
+        whitelist.max_lock_duration,
+        whitelist.bonus_multiplier,
+        &whitelist.tiers,
+    )
+}
+
+/// Tiers must raise the duration and never lower the multiplier, starting
+/// from the base `bonus_multiplier`
+fn validate_tiers(
+    bounds: &ConfigBounds,
+    max_lock_duration: u64,
+    bonus_multiplier: Decimal,
+    tiers: &[LockTier],
+) -> Result<(), ContractError> {
+    let invalid = ContractError::InvalidTiers { max: MAX_LOCK_TIERS };
+    if tiers.len() > MAX_LOCK_TIERS {
+        return Err(invalid);
     }
 
-    config.paused = false;
-    CONFIG.save(deps.storage, &config)?;
+    let mut previous: Option<&LockTier> = None;
+    for tier in tiers {
+        validate_bonus_multiplier(bounds, tier.multiplier)?;
 
-    Ok(Response::new().add_attribute("action", "resume"))
+        let ordered = match previous {
+            Some(previous) => {
+                tier.min_duration > previous.min_duration && tier.multiplier >= previous.multiplier
+            }
+            None => tier.multiplier >= bonus_multiplier,
+        };
+        if !ordered || tier.min_duration > max_lock_duration {
+            return Err(invalid);
+        }
+        previous = Some(tier);
+    }
+    Ok(())
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::Locker { locker_id } => to_json_binary(&query_locker(deps, locker_id)?),
-        QueryMsg::LockersByOwner { owner, start_after, limit } => {
-            to_json_binary(&query_lockers_by_owner(deps, owner, start_after, limit)?)
-        }
-        QueryMsg::WhitelistedLP { lp_token } => {
-            to_json_binary(&query_whitelisted_lp(deps, lp_token)?)
-        }
-        QueryMsg::AllWhitelistedLPs { start_after, limit } => {
-            to_json_binary(&query_all_whitelisted_lps(deps, start_after, limit)?)
-        }
-        QueryMsg::TotalLockedByLP { lp_token } => {
-            to_json_binary(&query_total_locked(deps, lp_token)?)
+/// Multiplier of the highest tier reached by `duration`
+fn tier_multiplier(whitelist: &WhitelistedLP, duration: u64) -> Decimal {
+    whitelist
+        .tiers
+        .iter()
+        .rev()
+        .find(|tier| duration >= tier.min_duration)
+        .map_or(whitelist.bonus_multiplier, |tier| tier.multiplier)
+}
+
+fn validate_metadata(metadata: &LockerMetadata) -> Result<(), ContractError> {
+    let text_fields = [
+        ("project_name", &metadata.project_name, MAX_PROJECT_NAME_LENGTH),
+        ("website", &metadata.website, MAX_URL_LENGTH),
+        ("logo_uri", &metadata.logo_uri, MAX_URL_LENGTH),
+        ("description", &metadata.description, MAX_DESCRIPTION_LENGTH),
+    ];
+    for (field, value, max) in text_fields {
+        if value.as_ref().is_some_and(|value| value.chars().count() > max) {
+            return Err(ContractError::MetadataTooLong {
+                field: field.to_string(),
+                max,
+            });
         }
     }
+
+    if let Some(website) = &metadata.website {
+        validate_url("website", website, &["https://"])?;
+    }
+
+    if let Some(logo_uri) = &metadata.logo_uri {
+        validate_url("logo_uri", logo_uri, &["https://", "ipfs://"])?;
+    }
+
+    if metadata.tags.len() > MAX_TAGS {
+        return Err(ContractError::TooManyTags { max: MAX_TAGS });
+    }
+
+    if metadata.tags.iter().any(|tag| tag.chars().count() > MAX_TAG_LENGTH) {
+        return Err(ContractError::MetadataTooLong {
+            field: "tags".to_string(),
+            max: MAX_TAG_LENGTH,
+        });
+    }
+    Ok(())
 }
 
-fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
-    let config = CONFIG.load(deps.storage)?;
-    Ok(ConfigResponse {
-        admin: config.admin,
-        reward_controller: config.reward_controller,
-        emergency_unlock_delay: config.emergency_unlock_delay,
-        platform_fee_bps: config.platform_fee_bps,
-        paused: config.paused,
-        next_locker_id: config.next_locker_id,
-    })
+/// Requires one of `schemes`, a non-empty host and no whitespace or control characters
+fn validate_url(field: &str, url: &str, schemes: &[&str]) -> Result<(), ContractError> {
+    let has_host = schemes.iter().any(|scheme| {
+        url.strip_prefix(scheme)
+            .and_then(|rest| rest.split(['/', '?', '#']).next())
+            .is_some_and(|host| !host.is_empty())
+    });
+
+    if !has_host || url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(ContractError::InvalidMetadataUrl {
+            field: field.to_string(),
+        });
+    }
+    Ok(())
 }
 
-fn query_locker(deps: Deps, locker_id: u64) -> StdResult<LockerResponse> {
-    let locker = LOCKERS.load(deps.storage, locker_id)?;
-    Ok(LockerResponse {
+fn validate_lock_durations(min: u64, max: u64) -> Result<(), ContractError> {
+    if min > max {
+        return Err(ContractError::InvalidLockDurations { min, max });
+    }
+    Ok(())
+}
+
+fn validate_relock_duration(whitelist: &WhitelistedLP, duration: u64) -> Result<(), ContractError> {
+    if duration < whitelist.min_lock_duration || duration > whitelist.max_lock_duration {
+        return Err(ContractError::InvalidUnlockTime {
+            min: whitelist.min_lock_duration,
+            max: whitelist.max_lock_duration,
+        });
+    }
+    Ok(())
+}
+
+/// Unlock time as seen at `now`: rolling lockers always sit a full duration away
+fn effective_unlock_time(locker: &Locker, now: u64) -> u64 {
+    match locker.auto_relock {
+        Some(duration) => locker.unlock_time.max(now + duration),
+        None => locker.unlock_time,
+    }
+}
+
+fn locker_response(locker: Locker, now: u64) -> LockerResponse {
+    let unlock_time = effective_unlock_time(&locker, now);
+    LockerResponse {
         id: locker.id,
         owner: locker.owner,
         lp_token: locker.lp_token,
         amount: locker.amount,
         locked_at: locker.locked_at,
-        unlock_time: locker.unlock_time,
+        unlock_time,
         extended_count: locker.extended_count,
         emergency_unlock_requested: locker.emergency_unlock_requested,
         metadata: locker.metadata,
-    })
-}
-
-fn query_lockers_by_owner(
-    deps: Deps,
-    owner: String,
-    start_after: Option<u64>,
-    limit: Option<u32>,
-) -> StdResult<LockersResponse> {
-    let owner_addr = deps.api.addr_validate(&owner)?;
-    let limit = limit.unwrap_or(10).min(30) as usize;
-    let start = start_after.map(|id| Bound::exclusive((&owner_addr, id)));
-
-    let lockers: Vec<LockerResponse> = USER_LOCKERS
-        .prefix(&owner_addr)
-        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
-        .take(limit)
-        .filter_map(|item| {
-            item.ok().and_then(|(locker_id, _)| {
-                LOCKERS.load(deps.storage, locker_id).ok().map(|locker| {
-                    LockerResponse {
-                        id: locker.id,
-                        owner: locker.owner,
-                        lp_token: locker.lp_token,
-                        amount: locker.amount,
-                        locked_at: locker.locked_at,
-                        unlock_time: locker.unlock_time,
-                        extended_count: locker.extended_count,
-                        emergency_unlock_requested: locker.emergency_unlock_requested,
-                        metadata: locker.metadata,
-                    }
-                })
-            })
-        })
-        .collect();
-
-    Ok(LockersResponse { lockers })
+        auto_relock: locker.auto_relock,
+        bonus_multiplier: locker.bonus_multiplier,
+    }
 }
 
-fn query_whitelisted_lp(deps: Deps, lp_token: String) -> StdResult<WhitelistedLPResponse> {
-    let lp_addr = deps.api.addr_validate(&lp_token)?;
-    let whitelist = WHITELISTED_LPS.load(deps.storage, &lp_addr)?;
-    
-    Ok(WhitelistedLPResponse {
+fn whitelisted_lp_response(whitelist: WhitelistedLP) -> WhitelistedLPResponse {
+    WhitelistedLPResponse {
         lp_token: whitelist.lp_token,
         min_lock_duration: whitelist.min_lock_duration,
         max_lock_duration: whitelist.max_lock_duration,
         enabled: whitelist.enabled,
         bonus_multiplier: whitelist.bonus_multiplier,
-    })
+        tiers: whitelist.tiers,
+        paused: whitelist.paused,
+        receipt_token: whitelist.receipt_token,
+        pair: whitelist.pair,
+        limits: whitelist.limits,
+    }
 }
 
-fn query_all_whitelisted_lps(
+/// Accepts the locker owner or an unexpired operator holding `permission`
+fn ensure_authorized(
     deps: Deps,
-    start_after: Option<String>,
-    limit: Option<u32>,
-) -> StdResult<Vec<WhitelistedLPResponse>> {
-    let limit = limit.unwrap_or(10).min(30) as usize;
-    let start = start_after.as_ref().map(|s| {
-        deps.api.addr_validate(s).map(|addr| Bound::exclusive(&addr))
-    }).transpose()?;
+    env: &Env,
+    locker: &Locker,
+    sender: &Addr,
+    permission: OperatorPermission,
+) -> Result<(), ContractError> {
+    if locker.owner == *sender {
+        return Ok(());
+    }
 
-    WHITELISTED_LPS
-        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
-        .take(limit)
-        .map(|item| {
-            let (_, whitelist) = item?;
-            Ok(WhitelistedLPResponse {
-                lp_token: whitelist.lp_token,
-                min_lock_duration: whitelist.min_lock_duration,
-                max_lock_duration: whitelist.max_lock_duration,
-                enabled: whitelist.enabled,
-                bonus_multiplier: whitelist.bonus_multiplier,
+    let approved = OPERATORS
+        .may_load(deps.storage, (&locker.owner, sender))?
+        .is_some_and(|approval| {
+            !approval.expires.is_expired(&env.block) && approval.permissions.contains(&permission)
+        });
+
+    if !approved {
+        return Err(ContractError::NotOwner {});
+    }
+    Ok(())
+}
+
+fn mint_receipt_msg(
+    whitelist: &WhitelistedLP,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<Option<WasmMsg>> {
+    whitelist
+        .receipt_token
+        .as_ref()
+        .map(|receipt_token| {
+            Ok(WasmMsg::Execute {
+                contract_addr: receipt_token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
             })
         })
-        .collect()
+        .transpose()
 }
 
-fn query_total_locked(deps: Deps, lp_token: String) -> StdResult<TotalLockedResponse> {
-    let lp_addr = deps.api.addr_validate(&lp_token)?;
-    let total = TOTAL_LOCKED.may_load(deps.storage, &lp_addr)?.unwrap_or_default();
-    
-    Ok(TotalLockedResponse {
-        lp_token: lp_addr,
-        total_amount: total,
-    })
+fn load_receipt_token(deps: Deps, lp_token: &Addr) -> StdResult<Option<Addr>> {
+    Ok(WHITELISTED_LPS
+        .may_load(deps.storage, lp_token)?
+        .and_then(|whitelist| whitelist.receipt_token))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(
-    deps: DepsMut,
-    _env: Env,
-    msg: MigrateMsg,
-) -> Result<Response, ContractError> {
-    let version = cw2::get_contract_version(deps.storage)?;
-    
-    if version.contract != CONTRACT_NAME {
-        return Err(ContractError::InvalidMigration {});
+fn ensure_receipt_returned(
+    deps: Deps,
+    locker: &Locker,
+    receipt_returned: bool,
+) -> Result<(), ContractError> {
+    if !receipt_returned && load_receipt_token(deps, &locker.lp_token)?.is_some() {
+        return Err(ContractError::ReceiptRequired {});
     }
+    Ok(())
+}
 
-    match msg {
-        MigrateMsg::V1ToV2 { reward_controller } => {
-            let mut config = CONFIG.load(deps.storage)?;
-            
-            if let Some(addr) = reward_controller {
-                config.reward_controller = Some(deps.api.addr_validate(&addr)?);
-                CONFIG.save(deps.storage, &config)?;
-            }
+fn unlocked(locker: &Locker, kind: UnlockKind) -> events::Unlocked {
+    events::Unlocked {
+        locker_id: locker.id,
+        owner: locker.owner.clone(),
+        recipient: locker.owner.clone(),
+        lp_token: locker.lp_token.clone(),
+        amount: locker.amount,
+        unlock_time: locker.unlock_time,
+        kind,
+        tip: Uint128::zero(),
+    }
+}
 
-            cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+fn unlocked_event(locker: &Locker, kind: UnlockKind) -> Event {
+    Event::from(unlocked(locker, kind))
+}
 
-            Ok(Response::new()
-                .add_attribute("action", "migrate")
-                .add_attribute("from_version", version.version)
-                .add_attribute("to_version", CONTRACT_VERSION))
-        }
-    }
+/// Balance of a CW20 held by the contract above what lockers hold
+fn lp_surplus(deps: Deps, env: &Env, token: &Addr) -> StdResult<Uint128> {
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+    let locked = TOTAL_LOCKED.may_load(deps.storage, token)?.unwrap_or_default();
+    Ok(balance.balance.saturating_sub(locked))
+}
+
+fn transfer_lp_msg(locker: &Locker) -> StdResult<WasmMsg> {
+    cw20_transfer_msg(&locker.lp_token, &locker.owner, locker.amount)
+}
+
+/// Notifies the reward controller, when one is configured, of a locker change
+fn reward_controller_msg(
+    storage: &dyn Storage,
+    msg: &RewardControllerExecuteMsg,
+) -> StdResult<Option<WasmMsg>> {
+    CONFIG
+        .load(storage)?
+        .reward_controller
+        .map(|reward_controller| -> StdResult<_> {
+            Ok(WasmMsg::Execute {
+                contract_addr: reward_controller.to_string(),
+                msg: to_json_binary(msg)?,
+                funds: vec![],
+            })
+        })
+        .transpose()
+}
+
+/// Reward stakes must not outlive their lockers
+fn lockers_closed_msg(storage: &dyn Storage, locker_ids: Vec<u64>) -> StdResult<Option<WasmMsg>> {
+    reward_controller_msg(storage, &RewardControllerExecuteMsg::LockersClosed { locker_ids })
+}
+
+fn cw20_transfer_msg(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
+
+fn join_ids(ids: &[u64]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")
 }
 
-// Helper function
 use cosmwasm_std::from_json;
 use cw_storage_plus::Bound;
+use semver::Version;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,15 +6,51 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("Contract is paused")]
     Paused {},
 
+    #[error("LP token is paused")]
+    LPPaused {},
+
     #[error("LP token not whitelisted")]
     LPNotWhitelisted {},
 
+    #[error("LP token already whitelisted")]
+    LPAlreadyWhitelisted {},
+
+    #[error("LP token still has locked funds")]
+    LPHasActiveLocks {},
+
+    #[error("Invalid lock durations: min {min} exceeds max {max}")]
+    InvalidLockDurations { min: u64, max: u64 },
+
+    #[error("Emergency unlock delay must be between {min} and {max} seconds")]
+    InvalidEmergencyUnlockDelay { min: u64, max: u64 },
+
+    #[error("Platform fee cannot exceed {max} bps")]
+    PlatformFeeTooHigh { max: u16 },
+
+    #[error("Bonus multiplier must be between 1 and {max}")]
+    InvalidBonusMultiplier { max: Decimal },
+
+    #[error("Bounds exceed the hard limits or exclude the current config")]
+    InvalidBounds {},
+
+    #[error("LP token is not the liquidity token of a trusted factory pair")]
+    UnverifiedLP {},
+
+    #[error("Pair contract is not the verified pair of this LP")]
+    PairMismatch {},
+
+    #[error("Factory does not allow permissionless whitelisting")]
+    PermissionlessDisabled {},
+
     #[error("Locker not found")]
     LockerNotFound {},
 
@@ -39,6 +75,102 @@ pub enum ContractError {
     #[error("Invalid migration")]
     InvalidMigration {},
 
+    #[error("Cannot migrate from version {from} to older version {to}")]
+    MigrationDowngrade { from: String, to: String },
+
     #[error("Amount must be greater than zero")]
     ZeroAmount {},
+
+    #[error("Auto-relock is enabled; disable it to start the unlock countdown")]
+    AutoRelockEnabled {},
+
+    #[error("Auto-relock is not enabled")]
+    AutoRelockDisabled {},
+
+    #[error("Sent token does not match the locker's LP token")]
+    TokenMismatch {},
+
+    #[error("Cannot approve yourself as operator")]
+    InvalidOperator {},
+
+    #[error("Transfer approvals must name their recipients, and only transfer approvals may")]
+    InvalidTransferRecipients {},
+
+    #[error("Owner has not approved this transfer recipient")]
+    RecipientNotApproved {},
+
+    #[error("Receipt tokens must be returned to unlock this locker")]
+    ReceiptRequired {},
+
+    #[error("Sent token is not the receipt token of this locker")]
+    InvalidReceipt {},
+
+    #[error("Receipt amount must equal the locked amount ({expected})")]
+    InvalidReceiptAmount { expected: Uint128 },
+
+    #[error("Locker contract must be the receipt token minter")]
+    ReceiptMinterMismatch {},
+
+    #[error("Only {available} can be recovered without touching locked funds")]
+    InsufficientSurplus { available: Uint128 },
+
+    #[error("Invalid lock tiers: at most {max}, longer and not lower than the previous tier")]
+    InvalidTiers { max: usize },
+
+    #[error("Metadata field {field} exceeds {max} characters")]
+    MetadataTooLong { field: String, max: usize },
+
+    #[error("Metadata field {field} is not a valid URL")]
+    InvalidMetadataUrl { field: String },
+
+    #[error("At most {max} metadata tags are allowed")]
+    TooManyTags { max: usize },
+
+    #[error("Keeper tip cannot exceed the platform fee ({max} bps)")]
+    KeeperTipTooHigh { max: u16 },
+
+    #[error("Owner does not allow keepers to release this locker")]
+    KeeperReleaseDisabled {},
+
+    #[error("Lock amount is below the minimum of {min}")]
+    BelowMinLockAmount { min: Uint128 },
+
+    #[error("Total locked for this LP cannot exceed {cap}")]
+    LockCapExceeded { cap: Uint128 },
+
+    #[error("Owner already holds the maximum of {max} lockers for this LP")]
+    TooManyLockers { max: u32 },
+
+    #[error("Owner already holds the maximum of {max} lockers")]
+    TooManyOwnerLockers { max: u32 },
+
+    #[error("Address {address} is blocked")]
+    Blocked { address: String },
+
+    #[error("Locker owner is not blocked")]
+    NotBlocked {},
+
+    #[error("No recovery address is set")]
+    NoRecoveryAddress {},
+
+    #[error("Inactivity period must be between {min} and {max} seconds")]
+    InvalidInactivityPeriod { min: u64, max: u64 },
+
+    #[error("Locker has no beneficiary")]
+    NoBeneficiary {},
+
+    #[error("Owner is still active; claimable by the beneficiary at {0}")]
+    OwnerStillActive(u64),
+
+    #[error("Batch must contain at least one locker")]
+    EmptyBatch {},
+
+    #[error("Batch too large: at most {max} lockers")]
+    BatchTooLarge { max: usize },
+
+    #[error("Duplicate locker in batch: {0}")]
+    DuplicateLocker(u64),
+
+    #[error("Locker {locker_id} failed: {reason}")]
+    BatchFailed { locker_id: u64, reason: String },
 }
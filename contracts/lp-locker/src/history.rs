@@ -0,0 +1,106 @@
+//! Append-only event history per locker.
+//!
+//! Entries are never changed or removed, not even after the locker is
+//! unlocked, so the full life of a lock can be audited.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Env, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Map};
+
+#[cw_serde]
+pub enum LockerEventKind {
+    Created {
+        owner: Addr,
+        amount: Uint128,
+        unlock_time: u64,
+    },
+    Extended {
+        old_unlock_time: u64,
+        new_unlock_time: u64,
+    },
+    ToppedUp {
+        amount: Uint128,
+        new_amount: Uint128,
+    },
+    Transferred {
+        from: Addr,
+        to: Addr,
+    },
+    /// Passed to the beneficiary of an inactive owner
+    Inherited {
+        from: Addr,
+        to: Addr,
+    },
+    AutoRelockEnabled {
+        duration: u64,
+    },
+    AutoRelockDisabled {
+        unlock_time: u64,
+    },
+    EmergencyRequested {
+        execute_at: u64,
+    },
+    EmergencyCancelled {},
+    MetadataUpdated {},
+    Unlocked {
+        amount: Uint128,
+        emergency: bool,
+    },
+    /// Released by chain governance through `sudo`
+    ForceReleased {
+        amount: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct LockerEvent {
+    /// Position in the locker's history, starting at 0
+    pub id: u64,
+    pub kind: LockerEventKind,
+    pub time: u64,
+    pub height: u64,
+}
+
+/// Events keyed by (locker_id, event id)
+pub const LOCKER_HISTORY: Map<(u64, u64), LockerEvent> = Map::new("locker_history");
+
+/// Appends an event at the current block to the history of `locker_id`
+pub fn record(
+    storage: &mut dyn Storage,
+    env: &Env,
+    locker_id: u64,
+    kind: LockerEventKind,
+) -> StdResult<()> {
+    let id = LOCKER_HISTORY
+        .prefix(locker_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+
+    LOCKER_HISTORY.save(
+        storage,
+        (locker_id, id),
+        &LockerEvent {
+            id,
+            kind,
+            time: env.block.time.seconds(),
+            height: env.block.height,
+        },
+    )
+}
+
+/// Events of `locker_id` in the order they happened
+pub fn events(
+    storage: &dyn Storage,
+    locker_id: u64,
+    start_after: Option<u64>,
+    limit: usize,
+) -> StdResult<Vec<LockerEvent>> {
+    LOCKER_HISTORY
+        .prefix(locker_id)
+        .range(storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, event)| event))
+        .collect()
+}
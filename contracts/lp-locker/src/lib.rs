@@ -1,7 +1,10 @@
 pub mod contract;
 pub mod error;
+pub mod history;
+pub mod migrations;
 pub mod msg;
 pub mod state;
+pub mod ve;
 
 #[cfg(test)]
 mod tests;
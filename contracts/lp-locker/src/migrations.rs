@@ -0,0 +1,164 @@
+//! Versioned state migrations.
+//!
+//! Each step upgrades the state written by the previous release. `migrate`
+//! runs, in order, every step whose version is newer than the stored contract
+//! version and not newer than the version being deployed.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, Order, StdError, StdResult, Uint128};
+use cw_storage_plus::Map;
+use semver::Version;
+
+use crate::state::{
+    Locker, LockerMetadata, LOCKED_BALANCES, LOCKERS, MATURITY_INDEX, MAX_DESCRIPTION_LENGTH,
+    OWNER_LOCKER_COUNT, OWNER_LP_MATURITY_INDEX, OWNER_MATURITY_INDEX, OWNER_TOTALS,
+    WHITELISTED_LPS,
+};
+use crate::ve;
+
+pub struct Migration {
+    pub version: &'static str,
+    pub name: &'static str,
+    pub run: fn(DepsMut, &Env) -> StdResult<()>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: "2.1.0",
+        name: "structure_locker_metadata",
+        run: structure_locker_metadata,
+    },
+    Migration {
+        version: "2.1.0",
+        name: "backfill_locker_indexes",
+        run: backfill_locker_indexes,
+    },
+];
+
+/// Applies every step in `(from, to]` and returns the names of the applied steps
+pub fn run(mut deps: DepsMut, env: &Env, from: &Version, to: &Version) -> StdResult<Vec<String>> {
+    let mut applied = vec![];
+    for migration in MIGRATIONS {
+        let version = Version::parse(migration.version)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+        if version > *from && version <= *to {
+            (migration.run)(deps.branch(), env)?;
+            applied.push(migration.name.to_string());
+        }
+    }
+    Ok(applied)
+}
+
+/// `Locker` as stored by 2.0.0, with free-form metadata
+#[cw_serde]
+struct LegacyLocker {
+    id: u64,
+    owner: Addr,
+    lp_token: Addr,
+    amount: Uint128,
+    locked_at: u64,
+    unlock_time: u64,
+    extended_count: u8,
+    emergency_unlock_requested: Option<u64>,
+    metadata: Option<String>,
+}
+
+const LEGACY_LOCKERS: Map<u64, LegacyLocker> = Map::new("lockers");
+
+/// 2.0.0 -> 2.1.0: free-form metadata becomes the description of the
+/// structured metadata, truncated to the description limit. The LP's flat
+/// multiplier is stored on each locker.
+fn structure_locker_metadata(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    let lockers: Vec<LegacyLocker> = LEGACY_LOCKERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, locker)| locker))
+        .collect::<StdResult<_>>()?;
+
+    for locker in lockers {
+        // Tiers did not exist, so every locker had its LP's flat multiplier
+        let bonus_multiplier = WHITELISTED_LPS
+            .may_load(deps.storage, &locker.lp_token)?
+            .map_or(Decimal::one(), |whitelist| whitelist.bonus_multiplier);
+        let metadata = locker.metadata.map(|text| LockerMetadata {
+            description: Some(text.chars().take(MAX_DESCRIPTION_LENGTH).collect()),
+            ..LockerMetadata::default()
+        });
+
+        LOCKERS.save(
+            deps.storage,
+            locker.id,
+            &Locker {
+                id: locker.id,
+                owner: locker.owner,
+                lp_token: locker.lp_token,
+                amount: locker.amount,
+                locked_at: locker.locked_at,
+                unlock_time: locker.unlock_time,
+                extended_count: locker.extended_count,
+                emergency_unlock_requested: locker.emergency_unlock_requested,
+                metadata,
+                auto_relock: None,
+                bonus_multiplier,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// 2.0.0 -> 2.1.0: per-owner balances and totals, the maturity index and
+/// voting power did not exist before 2.1.0, so they are rebuilt from the
+/// stored lockers. New `Locker`, `WhitelistedLP` and `Config` fields load with
+/// their serde defaults.
+fn backfill_locker_indexes(deps: DepsMut, env: &Env) -> StdResult<()> {
+    let lockers: Vec<Locker> = LOCKERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, locker)| locker))
+        .collect::<StdResult<_>>()?;
+
+    for locker in lockers {
+        LOCKED_BALANCES.update(
+            deps.storage,
+            (&locker.owner, &locker.lp_token),
+            env.block.height,
+            |balance| -> StdResult<Uint128> {
+                Ok(balance.unwrap_or_default().checked_add(locker.amount)?)
+            },
+        )?;
+        OWNER_TOTALS.update(
+            deps.storage,
+            (&locker.owner, &locker.lp_token),
+            |totals| -> StdResult<_> {
+                let mut totals = totals.unwrap_or_default();
+                totals.add(&locker)?;
+                Ok(totals)
+            },
+        )?;
+        OWNER_LOCKER_COUNT.update(deps.storage, &locker.owner, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default() + 1)
+        })?;
+
+        // 2.0.0 had no auto-relock, so every locker can mature
+        let maturity = (locker.unlock_time, locker.id);
+        MATURITY_INDEX.save(deps.storage, maturity, &true)?;
+        OWNER_MATURITY_INDEX.save(
+            deps.storage,
+            (&locker.owner, locker.unlock_time, locker.id),
+            &true,
+        )?;
+        OWNER_LP_MATURITY_INDEX.save(
+            deps.storage,
+            (&locker.owner, &locker.lp_token, maturity),
+            &true,
+        )?;
+
+        let max_lock_duration = WHITELISTED_LPS
+            .may_load(deps.storage, &locker.lp_token)?
+            .map(|whitelist| whitelist.max_lock_duration);
+        if max_lock_duration.is_some() {
+            ve::checkpoint_locker(deps.storage, env.block.time.seconds(), &locker, max_lock_duration)?;
+        }
+    }
+
+    Ok(())
+}
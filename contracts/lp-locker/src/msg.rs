@@ -1,6 +1,13 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ReceiveMsg, Expiration};
+use serde::{Deserialize, Serialize};
+
+use crate::history::LockerEvent;
+use crate::state::{
+    AssetInfo, Beneficiary, ConfigBounds, LPDefaults, LPPair, LockLimits, LockTier, LockerMetadata,
+    MigrationRecord, OperatorPermission, PauseFlags, Screening,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -22,18 +29,98 @@ pub enum ExecuteMsg {
         new_unlock_time: u64,
     },
     
+    /// Extend lock duration by `additional_seconds`, counted from the current
+    /// unlock time or from now if the locker has already matured
+    ExtendLockBy {
+        locker_id: u64,
+        additional_seconds: u64,
+    },
+    
+    /// Unlock a matured locker by sending its LP tokens to `pair_contract` for
+    /// withdrawal, paying the underlying assets to the owner
+    UnlockAndWithdrawLiquidity {
+        locker_id: u64,
+        pair_contract: String,
+        min_assets: Vec<Asset>,
+    },
+    
+    /// Unlock several matured lockers at once (all-or-nothing)
+    UnlockMany { locker_ids: Vec<u64> },
+    
+    /// Release matured lockers to their owners (all-or-nothing). Callable by
+    /// anyone; callers other than the owner earn the keeper tip
+    ReleaseMatured { locker_ids: Vec<u64> },
+    
+    /// Allow or refuse the release of your matured lockers through `ReleaseMatured`
+    SetKeeperRelease { enabled: bool },
+    
+    /// Extend several lockers to the same unlock time (all-or-nothing)
+    ExtendMany {
+        locker_ids: Vec<u64>,
+        new_unlock_time: u64,
+    },
+    
+    /// Keep the locker locked for a rolling duration until disabled
+    EnableAutoRelock { locker_id: u64, duration: u64 },
+    
+    /// Stop rolling the lock and start a normal countdown of the rolling duration
+    DisableAutoRelock { locker_id: u64 },
+    
+    /// Replace or clear the metadata of a locker (owner only)
+    UpdateLockerMetadata {
+        locker_id: u64,
+        metadata: Option<LockerMetadata>,
+    },
+    
+    /// Transfer locker ownership (owner only)
+    TransferLocker { locker_id: u64, recipient: String },
+    
+    /// Let an operator manage all of the sender's lockers within `permissions`
+    ApproveOperator {
+        operator: String,
+        permissions: Vec<OperatorPermission>,
+        expires: Option<Expiration>,
+        /// Required with `Transfer`: the only addresses the operator may transfer to
+        transfer_recipients: Option<Vec<String>>,
+    },
+    
+    /// Remove an operator approval
+    RevokeOperator { operator: String },
+    
     /// Request emergency unlock (starts delay timer)
     RequestEmergencyUnlock { locker_id: u64 },
     
+    /// Withdraw a pending emergency unlock request
+    CancelEmergencyUnlock { locker_id: u64 },
+    
     /// Execute emergency unlock (after delay)
     ExecuteEmergencyUnlock { locker_id: u64 },
     
+    /// Name who may claim one locker (or, without `locker_id`, all of the
+    /// sender's lockers) after `inactivity_period` seconds of owner inactivity
+    /// past the unlock time
+    SetBeneficiary {
+        locker_id: Option<u64>,
+        beneficiary: String,
+        inactivity_period: u64,
+    },
+    
+    /// Remove the beneficiary of one locker, or the sender's default one
+    RemoveBeneficiary { locker_id: Option<u64> },
+    
+    /// Prove the sender is still active; any other call does the same
+    Heartbeat {},
+    
+    /// Beneficiary: Take over a locker of an inactive owner
+    ClaimInheritance { locker_id: u64 },
+    
     /// Admin: Update configuration
     UpdateConfig {
         admin: Option<String>,
         reward_controller: Option<String>,
         emergency_unlock_delay: Option<u64>,
         platform_fee_bps: Option<u16>,
+        keeper_tip_bps: Option<u16>,
     },
     
     /// Admin: Whitelist LP token
@@ -42,9 +129,41 @@ pub enum ExecuteMsg {
         min_lock_duration: u64,
         max_lock_duration: u64,
         bonus_multiplier: Decimal,
+        /// Higher multipliers for longer locks; `bonus_multiplier` applies below the first tier
+        tiers: Option<Vec<LockTier>>,
+        /// When set, the LP token must be this pair's liquidity token and the
+        /// pair must belong to a trusted factory
+        pair_contract: Option<String>,
     },
     
-    /// Admin: Remove LP from whitelist
+    /// Whitelist the liquidity token of a trusted factory pair with the
+    /// factory's default parameters (anyone, if the factory allows it)
+    WhitelistLPFromFactory { pair_contract: String },
+    
+    /// Admin: Trust a DEX factory, optionally allowing permissionless whitelisting
+    AddTrustedFactory {
+        factory: String,
+        permissionless: Option<LPDefaults>,
+    },
+    
+    /// Admin: Stop trusting a DEX factory; already whitelisted LPs are kept
+    RemoveTrustedFactory { factory: String },
+    
+    /// Admin: Update whitelisted LP parameters, keeping existing lockers intact
+    UpdateLP {
+        lp_token: String,
+        min_lock_duration: Option<u64>,
+        max_lock_duration: Option<u64>,
+        bonus_multiplier: Option<Decimal>,
+        /// Replaces all tiers; existing lockers keep their multiplier
+        tiers: Option<Vec<LockTier>>,
+        enabled: Option<bool>,
+    },
+    
+    /// Admin: Stop new locks for an LP (existing lockers keep working)
+    DisableLP { lp_token: String },
+    
+    /// Admin: Remove LP from whitelist (only when nothing is locked)
     RemoveLP { lp_token: String },
     
     /// Admin: Pause contract
@@ -52,6 +171,53 @@ pub enum ExecuteMsg {
     
     /// Admin: Resume contract
     Resume {},
+    
+    /// Admin: Pause or resume individual operations
+    SetPauseFlags {
+        locks: Option<bool>,
+        extends: Option<bool>,
+        emergency_requests: Option<bool>,
+        transfers: Option<bool>,
+        fee_withdrawal: Option<bool>,
+    },
+    
+    /// Admin: Pause or resume a single LP token
+    SetLPPaused { lp_token: String, paused: bool },
+    
+    /// Admin: Set the default limits, or the overrides of one LP when
+    /// `lp_token` is given
+    SetLockLimits {
+        lp_token: Option<String>,
+        limits: LockLimits,
+    },
+    
+    /// Admin: Register the CW20 receipt token minted for an LP (locker must be its minter)
+    SetReceiptToken {
+        lp_token: String,
+        receipt_token: Option<String>,
+    },
+    
+    /// Admin: Recover tokens held above the locked amount (never touches locked funds)
+    RecoverTokens {
+        asset: AssetInfo,
+        amount: Uint128,
+        recipient: String,
+    },
+    
+    /// Admin: Add addresses to, or remove them from, the blocklist
+    UpdateBlocklist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    
+    /// Admin: Set or clear the screening contract and the recovery address
+    SetScreening {
+        contract: Option<String>,
+        recovery_address: Option<String>,
+    },
+    
+    /// Admin: Release matured lockers of blocked owners to the recovery address
+    RecoverBlockedLockers { locker_ids: Vec<u64> },
 }
 
 #[cw_serde]
@@ -59,8 +225,27 @@ pub enum Cw20HookMsg {
     /// Lock LP tokens
     LockLP {
         unlock_time: u64,
-        metadata: Option<String>,
+        metadata: Option<LockerMetadata>,
+        /// Optional rolling lock duration in seconds
+        auto_relock: Option<u64>,
+    },
+    
+    /// Lock LP tokens for `lock_duration` seconds from the block time of inclusion
+    LockLPFor {
+        lock_duration: u64,
+        metadata: Option<LockerMetadata>,
+        /// Optional rolling lock duration in seconds
+        auto_relock: Option<u64>,
     },
+    
+    /// Add LP tokens to an existing locker (owner or operator with `increase`)
+    IncreaseLock { locker_id: u64 },
+    
+    /// Return the receipt tokens of a locker and unlock it
+    UnlockLP { locker_id: u64 },
+    
+    /// Return the receipt tokens of a locker and execute its emergency unlock
+    ExecuteEmergencyUnlock { locker_id: u64 },
 }
 
 #[cw_serde]
@@ -79,17 +264,94 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     
+    /// Matured lockers ordered by unlock time. Without `owner`, only lockers
+    /// a keeper can release are listed. `start_after` is `(unlock_time, locker_id)`
+    #[returns(UnlockableLockersResponse)]
+    UnlockableLockers {
+        owner: Option<String>,
+        start_after: Option<(u64, u64)>,
+        limit: Option<u32>,
+    },
+    
+    /// Per-LP totals of everything `owner` has locked, paged by LP token
+    #[returns(OwnerPortfolioResponse)]
+    OwnerPortfolio {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    
     #[returns(WhitelistedLPResponse)]
     WhitelistedLP { lp_token: String },
     
+    /// Multiplier a lock of `duration` seconds would get
+    #[returns(TierForResponse)]
+    TierFor { lp_token: String, duration: u64 },
+    
     #[returns(Vec<WhitelistedLPResponse>)]
     AllWhitelistedLPs {
         start_after: Option<String>,
         limit: Option<u32>,
     },
     
+    /// Every change made to a locker, oldest first; kept after unlock
+    #[returns(LockerHistoryResponse)]
+    LockerHistory {
+        locker_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    
+    #[returns(Vec<TrustedFactoryResponse>)]
+    TrustedFactories {},
+    
     #[returns(TotalLockedResponse)]
     TotalLockedByLP { lp_token: String },
+    
+    /// Pro-rata share of the pair reserves backing one locker
+    #[returns(LockedValueResponse)]
+    LockerValue { locker_id: u64 },
+    
+    /// Pro-rata share of the pair reserves backing all locked LP of a token
+    #[returns(LockedValueResponse)]
+    LockedValueByLP { lp_token: String },
+    
+    /// Locked balance of an owner for one LP at the start of block `height`
+    #[returns(LockedBalanceResponse)]
+    LockedBalanceAt {
+        owner: String,
+        lp_token: String,
+        height: u64,
+    },
+    
+    /// Total locked for one LP at the start of block `height`
+    #[returns(TotalLockedResponse)]
+    TotalLockedAt { lp_token: String, height: u64 },
+    
+    /// Vote-escrow power of an address (defaults to the current block time)
+    #[returns(VotingPowerResponse)]
+    VotingPower {
+        address: String,
+        at_time: Option<u64>,
+    },
+    
+    #[returns(VotingPowerResponse)]
+    TotalVotingPower { at_time: Option<u64> },
+    
+    #[returns(OperatorResponse)]
+    Operator { owner: String, operator: String },
+    
+    /// Beneficiary applying to a locker and when it can claim
+    #[returns(BeneficiaryResponse)]
+    Beneficiary { locker_id: u64 },
+    
+    /// Whether `address` is on the blocklist; same interface as
+    /// `ScreeningQueryMsg`, so this contract can screen for others
+    #[returns(ScreeningResponse)]
+    IsBlocked { address: String },
+    
+    #[returns(Vec<MigrationRecord>)]
+    MigrationHistory {},
 }
 
 // Response types
@@ -99,8 +361,13 @@ pub struct ConfigResponse {
     pub reward_controller: Option<Addr>,
     pub emergency_unlock_delay: u64,
     pub platform_fee_bps: u16,
+    pub keeper_tip_bps: u16,
     pub paused: bool,
+    pub pause: PauseFlags,
     pub next_locker_id: u64,
+    pub bounds: ConfigBounds,
+    pub limits: LockLimits,
+    pub screening: Screening,
 }
 
 #[cw_serde]
@@ -113,7 +380,9 @@ pub struct LockerResponse {
     pub unlock_time: u64,
     pub extended_count: u8,
     pub emergency_unlock_requested: Option<u64>,
-    pub metadata: Option<String>,
+    pub metadata: Option<LockerMetadata>,
+    pub auto_relock: Option<u64>,
+    pub bonus_multiplier: Decimal,
 }
 
 #[cw_serde]
@@ -121,6 +390,39 @@ pub struct LockersResponse {
     pub lockers: Vec<LockerResponse>,
 }
 
+#[cw_serde]
+pub struct UnlockableLockersResponse {
+    pub lockers: Vec<LockerResponse>,
+    /// `start_after` for the next page; `None` once every matured locker was scanned
+    pub start_after: Option<(u64, u64)>,
+}
+
+#[cw_serde]
+pub struct OwnerPortfolioResponse {
+    pub owner: Addr,
+    pub lockers: u32,
+    /// Earliest unlock time still in the future, across all LP tokens
+    pub next_unlock_time: Option<u64>,
+    pub entries: Vec<PortfolioEntry>,
+    /// `start_after` for the next page; `None` once every LP token was listed
+    pub start_after: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PortfolioEntry {
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub lockers: u32,
+    /// Auto-relocking lockers have no unlock time until relocking is disabled
+    pub next_unlock_time: Option<u64>,
+    /// Amount in matured lockers, unlockable right now
+    pub unlockable: Uint128,
+    /// `unlockable` stopped at the scan cap; `UnlockableLockers` lists the rest
+    pub unlockable_capped: bool,
+    /// Amount in lockers with a pending emergency unlock request
+    pub emergency_pending: Uint128,
+}
+
 #[cw_serde]
 pub struct WhitelistedLPResponse {
     pub lp_token: Addr,
@@ -128,6 +430,36 @@ pub struct WhitelistedLPResponse {
     pub max_lock_duration: u64,
     pub enabled: bool,
     pub bonus_multiplier: Decimal,
+    pub tiers: Vec<LockTier>,
+    pub paused: bool,
+    pub receipt_token: Option<Addr>,
+    pub pair: Option<LPPair>,
+    pub limits: LockLimits,
+}
+
+#[cw_serde]
+pub struct TierForResponse {
+    pub duration: u64,
+    pub multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct LockerHistoryResponse {
+    pub events: Vec<LockerEvent>,
+}
+
+#[cw_serde]
+pub struct TrustedFactoryResponse {
+    pub factory: Addr,
+    pub permissionless: Option<LPDefaults>,
+}
+
+/// `assets` are valued at the current pair reserves
+#[cw_serde]
+pub struct LockedValueResponse {
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub assets: Vec<Asset>,
 }
 
 #[cw_serde]
@@ -137,6 +469,118 @@ pub struct TotalLockedResponse {
 }
 
 #[cw_serde]
-pub enum MigrateMsg {
-    V1ToV2 { reward_controller: Option<String> },
+pub struct LockedBalanceResponse {
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub height: u64,
+}
+
+/// Empty `permissions` means no approval
+#[cw_serde]
+pub struct OperatorResponse {
+    pub owner: Addr,
+    pub operator: Addr,
+    pub permissions: Vec<OperatorPermission>,
+    pub expires: Expiration,
+    pub transfer_recipients: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct BeneficiaryResponse {
+    /// The locker's own beneficiary, else the owner's default one
+    pub beneficiary: Option<Beneficiary>,
+    pub last_activity: Option<u64>,
+    pub claimable_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct VotingPowerResponse {
+    pub power: Uint128,
+    pub at_time: u64,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    pub reward_controller: Option<String>,
+}
+
+/// Chain-governance powers, callable only through `sudo`
+#[cw_serde]
+pub enum SudoMsg {
+    /// Pause the contract; unlocks at maturity keep working
+    ForcePause {},
+    
+    /// Replace the admin, e.g. after the admin key is lost or compromised
+    UpdateAdmin { admin: String },
+    
+    /// Release lockers to their owners regardless of unlock time.
+    /// Lockers of LPs with a receipt token are refused.
+    ForceRelease { locker_ids: Vec<u64> },
+    
+    /// Change the limits enforced on admin-set parameters, within the hard limits
+    UpdateBounds { bounds: ConfigBounds },
+}
+
+// DEX pair interface (subset of the Paxi DEX pair API)
+#[cw_serde]
+pub enum PairCw20HookMsg {
+    /// Burn the sent LP tokens and pay the underlying assets to `receiver`
+    WithdrawLiquidity {
+        min_assets: Vec<Asset>,
+        receiver: Option<String>,
+    },
+}
+
+#[cw_serde]
+pub enum PairQueryMsg {
+    Pair {},
+    Pool {},
+}
+
+#[cw_serde]
+pub enum FactoryQueryMsg {
+    Pair { asset_infos: Vec<AssetInfo> },
+}
+
+// Reward controller interface
+#[cw_serde]
+pub enum RewardControllerExecuteMsg {
+    /// Drop the reward stake of a locker that changed owner
+    LockerTransferred { locker_id: u64 },
+    /// Re-read the lock terms of a locker, e.g. after auto-relock was disabled
+    LockerUpdated { locker_id: u64 },
+    /// Drop the reward stakes of unlocked lockers
+    LockersClosed { locker_ids: Vec<u64> },
+}
+
+/// Query interface expected from the screening contract
+#[cw_serde]
+pub enum ScreeningQueryMsg {
+    IsBlocked { address: String },
+}
+
+#[cw_serde]
+pub struct ScreeningResponse {
+    pub blocked: bool,
+}
+
+/// Fields of the pair info returned by both the pair and the factory
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PairInfo {
+    pub asset_infos: Vec<AssetInfo>,
+    pub contract_addr: Addr,
+    pub liquidity_token: Addr,
+}
+
+/// Fields of the pair's pool response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PoolResponse {
+    pub assets: Vec<Asset>,
+}
+
+#[cw_serde]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
 }
@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, OverflowError, OverflowOperation, StdError, StdResult, Uint128};
+use cw20::Expiration;
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 
 #[cw_serde]
 pub struct Config {
@@ -10,6 +11,96 @@ pub struct Config {
     pub platform_fee_bps: u16,
     pub paused: bool,
     pub next_locker_id: u64,
+    /// Per-operation switches, applied on top of the global `paused` flag
+    #[serde(default)]
+    pub pause: PauseFlags,
+    /// Limits on admin-set parameters, adjustable by governance only
+    #[serde(default)]
+    pub bounds: ConfigBounds,
+    /// Share of released LP paid to whoever runs `ReleaseMatured` for an
+    /// owner; never above `platform_fee_bps`
+    #[serde(default)]
+    pub keeper_tip_bps: u16,
+    /// Default limits for every LP; each LP can override them
+    #[serde(default)]
+    pub limits: LockLimits,
+    #[serde(default)]
+    pub screening: Screening,
+}
+
+/// Sanctions screening on top of the admin-managed `BLOCKLIST`
+#[cw_serde]
+#[derive(Default)]
+pub struct Screening {
+    /// External contract asked about every new lock owner and transfer recipient
+    pub contract: Option<Addr>,
+    /// Receives the matured LP of blocked owners
+    pub recovery_address: Option<Addr>,
+}
+
+/// Hard limits that no configuration can exceed
+pub const MIN_EMERGENCY_UNLOCK_DELAY: u64 = 86_400;
+pub const MAX_EMERGENCY_UNLOCK_DELAY: u64 = 30 * 86_400;
+pub const MAX_PLATFORM_FEE_BPS: u16 = 1_000;
+pub const MAX_BONUS_MULTIPLIER: Decimal = Decimal::raw(10_000_000_000_000_000_000);
+pub const MIN_INACTIVITY_PERIOD: u64 = 30 * 86_400;
+pub const MAX_INACTIVITY_PERIOD: u64 = 5 * 365 * 86_400;
+
+/// Abuse and risk limits on new locks; `None` means unlimited
+#[cw_serde]
+#[derive(Default)]
+pub struct LockLimits {
+    /// Smallest amount a new locker can hold
+    pub min_lock_amount: Option<Uint128>,
+    /// Cap on the total locked for an LP token
+    pub max_total_locked: Option<Uint128>,
+    /// Cap on the active lockers an owner holds: across every LP token when
+    /// set as a default, for that LP token when set as an override
+    pub max_lockers_per_owner: Option<u32>,
+}
+
+impl LockLimits {
+    /// These limits, with unset ones taken from `defaults`
+    pub fn or(&self, defaults: &LockLimits) -> LockLimits {
+        LockLimits {
+            min_lock_amount: self.min_lock_amount.or(defaults.min_lock_amount),
+            max_total_locked: self.max_total_locked.or(defaults.max_total_locked),
+            max_lockers_per_owner: self.max_lockers_per_owner.or(defaults.max_lockers_per_owner),
+        }
+    }
+}
+
+/// Configurable limits, always within the hard limits above
+#[cw_serde]
+pub struct ConfigBounds {
+    pub min_emergency_unlock_delay: u64,
+    pub max_emergency_unlock_delay: u64,
+    pub max_platform_fee_bps: u16,
+    pub max_bonus_multiplier: Decimal,
+}
+
+impl Default for ConfigBounds {
+    fn default() -> Self {
+        Self {
+            min_emergency_unlock_delay: MIN_EMERGENCY_UNLOCK_DELAY,
+            max_emergency_unlock_delay: MAX_EMERGENCY_UNLOCK_DELAY,
+            max_platform_fee_bps: MAX_PLATFORM_FEE_BPS,
+            max_bonus_multiplier: MAX_BONUS_MULTIPLIER,
+        }
+    }
+}
+
+/// Operations that can be paused individually. Unlock at maturity is never pausable.
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseFlags {
+    pub locks: bool,
+    pub extends: bool,
+    pub emergency_requests: bool,
+    pub transfers: bool,
+    /// Withdrawal of platform funds through `RecoverTokens`
+    #[serde(default)]
+    pub fee_withdrawal: bool,
 }
 
 #[cw_serde]
@@ -22,7 +113,60 @@ pub struct Locker {
     pub unlock_time: u64,
     pub extended_count: u8,
     pub emergency_unlock_requested: Option<u64>,
-    pub metadata: Option<String>,
+    pub metadata: Option<LockerMetadata>,
+    /// Rolling lock duration; while set the locker never matures
+    pub auto_relock: Option<u64>,
+    /// Multiplier of the tier reached at lock, extend or auto-relock time
+    pub bonus_multiplier: Decimal,
+}
+
+/// Running totals of one owner's lockers for one LP token
+#[cw_serde]
+#[derive(Default)]
+pub struct OwnerTotals {
+    pub lockers: u32,
+    /// Locked amount with a pending emergency unlock request
+    pub emergency_pending: Uint128,
+}
+
+impl OwnerTotals {
+    pub fn add(&mut self, locker: &Locker) -> StdResult<()> {
+        self.lockers += 1;
+        if locker.emergency_unlock_requested.is_some() {
+            self.emergency_pending = self.emergency_pending.checked_add(locker.amount)?;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, locker: &Locker) -> StdResult<()> {
+        self.lockers = self.lockers.checked_sub(1).ok_or_else(|| {
+            StdError::overflow(OverflowError::new(OverflowOperation::Sub, self.lockers, 1))
+        })?;
+        if locker.emergency_unlock_requested.is_some() {
+            self.emergency_pending = self.emergency_pending.checked_sub(locker.amount)?;
+        }
+        Ok(())
+    }
+}
+
+pub const MAX_PROJECT_NAME_LENGTH: usize = 64;
+pub const MAX_URL_LENGTH: usize = 256;
+pub const MAX_DESCRIPTION_LENGTH: usize = 512;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Display information about a locker, shown by explorers and the launchpad
+#[cw_serde]
+#[derive(Default)]
+pub struct LockerMetadata {
+    pub project_name: Option<String>,
+    /// `https://` URL
+    pub website: Option<String>,
+    /// `https://` or `ipfs://` URI
+    pub logo_uri: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[cw_serde]
@@ -31,11 +175,143 @@ pub struct WhitelistedLP {
     pub min_lock_duration: u64,
     pub max_lock_duration: u64,
     pub enabled: bool,
+    /// Multiplier for lock durations below the first tier
+    pub bonus_multiplier: Decimal,
+    /// Higher multipliers for longer locks, ordered by `min_duration`
+    #[serde(default)]
+    pub tiers: Vec<LockTier>,
+    /// Blocks locks, extends and emergency requests for this LP only
+    #[serde(default)]
+    pub paused: bool,
+    /// CW20 minted 1:1 on lock and burned on unlock, if registered
+    #[serde(default)]
+    pub receipt_token: Option<Addr>,
+    /// DEX pair the LP token was verified against, if any
+    #[serde(default)]
+    pub pair: Option<LPPair>,
+    /// Overrides of the `Config` limits for this LP
+    #[serde(default)]
+    pub limits: LockLimits,
+}
+
+pub const MAX_LOCK_TIERS: usize = 10;
+
+/// Applies to lock durations of at least `min_duration`, up to the next tier
+#[cw_serde]
+pub struct LockTier {
+    pub min_duration: u64,
+    pub multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct LPPair {
+    pub factory: Addr,
+    pub pair_contract: Addr,
+    pub asset_infos: Vec<AssetInfo>,
+}
+
+/// Parameters for LPs whitelisted permissionlessly from a trusted factory
+#[cw_serde]
+pub struct LPDefaults {
+    pub min_lock_duration: u64,
+    pub max_lock_duration: u64,
     pub bonus_multiplier: Decimal,
+    #[serde(default)]
+    pub tiers: Vec<LockTier>,
+}
+
+#[cw_serde]
+pub struct TrustedFactory {
+    /// When set, anyone can whitelist this factory's LPs with these parameters
+    pub permissionless: Option<LPDefaults>,
+}
+
+/// Locker actions an owner can delegate; withdrawals are never delegable
+#[cw_serde]
+#[derive(Eq, PartialOrd, Ord)]
+pub enum OperatorPermission {
+    Extend,
+    Increase,
+    RegisterRewards,
+    /// Only to the recipients named in the approval
+    Transfer,
+}
+
+#[cw_serde]
+pub struct OperatorApproval {
+    pub permissions: Vec<OperatorPermission>,
+    pub expires: Expiration,
+    /// Addresses the owner consented to receive lockers moved with `Transfer`
+    #[serde(default)]
+    pub transfer_recipients: Vec<Addr>,
+}
+
+/// Who may claim a matured locker once its owner has been inactive for
+/// `inactivity_period` seconds after the unlock time
+#[cw_serde]
+pub struct Beneficiary {
+    pub address: Addr,
+    pub inactivity_period: u64,
+}
+
+#[cw_serde]
+pub enum AssetInfo {
+    Cw20(Addr),
+    Native(String),
+}
+
+/// One applied migration, kept for auditing upgrades
+#[cw_serde]
+pub struct MigrationRecord {
+    pub from_version: String,
+    pub to_version: String,
+    pub steps: Vec<String>,
+    pub height: u64,
+    pub time: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const LOCKERS: Map<u64, Locker> = Map::new("lockers");
 pub const USER_LOCKERS: Map<(&Addr, u64), bool> = Map::new("user_lockers");
 pub const WHITELISTED_LPS: Map<&Addr, WhitelistedLP> = Map::new("whitelisted_lps");
-pub const TOTAL_LOCKED: Map<&Addr, Uint128> = Map::new("total_locked");
+pub const TOTAL_LOCKED: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "total_locked",
+    "total_locked__checkpoints",
+    "total_locked__changelog",
+    Strategy::EveryBlock,
+);
+/// Locked amount per (owner, lp_token), snapshotted for airdrops and governance
+pub const LOCKED_BALANCES: SnapshotMap<(&Addr, &Addr), Uint128> = SnapshotMap::new(
+    "locked_balances",
+    "locked_balances__checkpoints",
+    "locked_balances__changelog",
+    Strategy::EveryBlock,
+);
+/// Lockers that can mature, keyed by (unlock_time, locker_id); auto-relocking
+/// lockers are left out until relocking is disabled
+pub const MATURITY_INDEX: Map<(u64, u64), bool> = Map::new("maturity_index");
+/// `MATURITY_INDEX` per owner, keyed by (owner, unlock_time, locker_id)
+pub const OWNER_MATURITY_INDEX: Map<(&Addr, u64, u64), bool> = Map::new("owner_maturity_index");
+/// `MATURITY_INDEX` per owner and LP token, keyed by
+/// (owner, lp_token, (unlock_time, locker_id))
+pub const OWNER_LP_MATURITY_INDEX: Map<(&Addr, &Addr, (u64, u64)), bool> =
+    Map::new("owner_lp_maturity_index");
+/// Addresses barred from new locks and from moving locked LP out
+pub const BLOCKLIST: Map<&Addr, bool> = Map::new("blocklist");
+/// Beneficiary of a single locker, taking precedence over the owner's
+pub const LOCKER_BENEFICIARIES: Map<u64, Beneficiary> = Map::new("locker_beneficiaries");
+/// Beneficiary of every locker of an owner
+pub const OWNER_BENEFICIARIES: Map<&Addr, Beneficiary> = Map::new("owner_beneficiaries");
+/// Block time of the last call made by each locker owner
+pub const LAST_ACTIVITY: Map<&Addr, u64> = Map::new("last_activity");
+/// Owners who do not allow keepers to release their matured lockers
+pub const KEEPER_OPT_OUTS: Map<&Addr, bool> = Map::new("keeper_opt_outs");
+/// Running totals keyed by (owner, lp_token); entries go away with the last locker
+pub const OWNER_TOTALS: Map<(&Addr, &Addr), OwnerTotals> = Map::new("owner_totals");
+/// Active lockers of each owner across every LP token
+pub const OWNER_LOCKER_COUNT: Map<&Addr, u32> = Map::new("owner_locker_count");
+pub const TRUSTED_FACTORIES: Map<&Addr, TrustedFactory> = Map::new("trusted_factories");
+/// Operator approvals keyed by (owner, operator)
+pub const OPERATORS: Map<(&Addr, &Addr), OperatorApproval> = Map::new("operators");
+/// Migration history keyed by a sequence number, in the order migrations ran
+pub const MIGRATION_HISTORY: Map<u64, MigrationRecord> = Map::new("migration_history");
@@ -1,10 +1,2811 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, Decimal, Env,
+    MemoryStorage, OwnedDeps, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{
+    BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Expiration, MinterResponse,
+    TokenInfoResponse,
+};
 
-    #[test]
-    fn test_instantiate() {
-        // TODO: Add comprehensive tests
-        assert_eq!(1, 1);
+use crate::contract::{execute, instantiate, migrate, query, sudo};
+use crate::error::ContractError;
+use crate::history::LockerEventKind;
+use crate::msg::{
+    Asset, BeneficiaryResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, FactoryQueryMsg,
+    InstantiateMsg, LockedBalanceResponse, LockedValueResponse, LockerHistoryResponse,
+    LockerResponse, LockersResponse, MigrateMsg, OperatorResponse, OwnerPortfolioResponse,
+    PairInfo, PairQueryMsg, PoolResponse, PortfolioEntry, QueryMsg, RewardControllerExecuteMsg,
+    ScreeningQueryMsg, ScreeningResponse, SudoMsg, TierForResponse, TotalLockedResponse,
+    UnlockableLockersResponse, VotingPowerResponse, WhitelistedLPResponse,
+};
+use crate::state::{
+    AssetInfo, ConfigBounds, LPDefaults, LockLimits, LockTier, LockerMetadata, MigrationRecord,
+    OperatorPermission,
+};
+
+const ADMIN: &str = "admin";
+const USER: &str = "user";
+const LP_A: &str = "lp_token_a";
+const LP_B: &str = "lp_token_b";
+const LP_C: &str = "lp_token_c";
+const DAY: u64 = 86_400;
+
+type MockDeps = OwnedDeps<MemoryStorage, MockApi, MockQuerier>;
+
+fn setup() -> MockDeps {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        InstantiateMsg {
+            admin: ADMIN.to_string(),
+            emergency_unlock_delay: 3 * DAY,
+        },
+    )
+    .unwrap();
+
+    for lp_token in [LP_A, LP_B] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::WhitelistLP {
+                lp_token: lp_token.to_string(),
+                min_lock_duration: DAY,
+                max_lock_duration: 365 * DAY,
+                bonus_multiplier: Decimal::one(),
+                tiers: None,
+                pair_contract: None,
+            },
+        )
+        .unwrap();
+    }
+
+    deps
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn lock(deps: &mut MockDeps, owner: &str, lp_token: &str, amount: u128, duration: u64) -> u64 {
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(lp_token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: owner.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&Cw20HookMsg::LockLP {
+                unlock_time: env.block.time.seconds() + duration,
+                metadata: None,
+                auto_relock: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    res.attributes
+        .iter()
+        .find(|attr| attr.key == "locker_id")
+        .unwrap()
+        .value
+        .parse()
+        .unwrap()
+}
+
+fn query_locker(deps: &MockDeps, locker_id: u64) -> LockerResponse {
+    from_json(query(deps.as_ref(), mock_env(), QueryMsg::Locker { locker_id }).unwrap()).unwrap()
+}
+
+fn transfers(msgs: &[cosmwasm_std::SubMsg]) -> Vec<(String, String, Uint128)> {
+    msgs.iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                match from_json(msg).unwrap() {
+                    Cw20ExecuteMsg::Transfer { recipient, amount } => {
+                        Some((contract_addr.clone(), recipient, amount))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Minimal DEX pair: burns received LP 1:1 into two native assets
+mod mock_pair {
+    use cosmwasm_std::{
+        coins, from_json, BankMsg, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    };
+    use cw20::Cw20ReceiveMsg;
+
+    use crate::msg::PairCw20HookMsg;
+    use crate::state::AssetInfo;
+
+    pub const LP_TOKEN: &str = "lp_token_c";
+    pub const DENOMS: [&str; 2] = ["upaxi", "uusdc"];
+
+    pub fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: Cw20ReceiveMsg,
+    ) -> StdResult<Response> {
+        if info.sender != LP_TOKEN {
+            return Err(StdError::generic_err("unknown LP token"));
+        }
+
+        let PairCw20HookMsg::WithdrawLiquidity { min_assets, receiver } = from_json(&msg.msg)?;
+        for asset in &min_assets {
+            let known = match &asset.info {
+                AssetInfo::Native(denom) => DENOMS.contains(&denom.as_str()),
+                AssetInfo::Cw20(_) => false,
+            };
+            if !known || asset.amount > msg.amount {
+                return Err(StdError::generic_err("slippage"));
+            }
+        }
+
+        let receiver = receiver.unwrap_or(msg.sender);
+        Ok(Response::new().add_messages(DENOMS.map(|denom| BankMsg::Send {
+            to_address: receiver.clone(),
+            amount: coins(msg.amount.u128(), denom),
+        })))
+    }
+}
+
+#[test]
+fn test_instantiate() {
+    let deps = setup();
+
+    let config: crate::msg::ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.admin, ADMIN);
+    assert_eq!(config.emergency_unlock_delay, 3 * DAY);
+    assert_eq!(config.next_locker_id, 0);
+    assert!(!config.paused);
+}
+
+#[test]
+fn unlock_and_withdraw_liquidity_pays_underlying_to_owner() {
+    let mut deps = setup();
+    mock_dex(&mut deps);
+    whitelist_pair_lp(&mut deps);
+    let locker_id = lock(&mut deps, USER, LP_C, 100, DAY);
+    let unlock = |min_amount: u128| ExecuteMsg::UnlockAndWithdrawLiquidity {
+        locker_id,
+        pair_contract: "pair".to_string(),
+        min_assets: vec![Asset {
+            info: AssetInfo::Native("upaxi".to_string()),
+            amount: Uint128::new(min_amount),
+        }],
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), unlock(90)).unwrap_err();
+    assert_eq!(err, ContractError::StillLocked(mock_env().block.time.seconds() + DAY));
+
+    let mut res = execute(deps.as_mut(), env_at(DAY), mock_info(USER, &[]), unlock(90)).unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    // Deliver the CW20 Send to the pair the way the LP token would
+    let send = match res.messages.remove(0).msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+            assert_eq!(contract_addr, LP_C);
+            match from_json(msg).unwrap() {
+                Cw20ExecuteMsg::Send { contract, amount, msg } => {
+                    assert_eq!(contract, "pair");
+                    Cw20ReceiveMsg {
+                        sender: mock_env().contract.address.to_string(),
+                        amount,
+                        msg,
+                    }
+                }
+                other => panic!("unexpected message {other:?}"),
+            }
+        }
+        other => panic!("unexpected message {other:?}"),
+    };
+
+    let mut pair_deps = mock_dependencies();
+    let paid = mock_pair::execute(pair_deps.as_mut(), mock_env(), mock_info(LP_C, &[]), send)
+        .unwrap();
+    for (sub, denom) in paid.messages.iter().zip(mock_pair::DENOMS) {
+        assert_eq!(
+            sub.msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER.to_string(),
+                amount: coins(100, denom),
+            })
+        );
+    }
+
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::Locker { locker_id }).is_err());
+}
+
+#[test]
+fn unlock_many_groups_transfers_per_lp_token() {
+    let mut deps = setup();
+    let first = lock(&mut deps, USER, LP_A, 100, DAY);
+    let second = lock(&mut deps, USER, LP_A, 50, DAY);
+    let third = lock(&mut deps, USER, LP_B, 70, DAY);
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockMany {
+            locker_ids: vec![first, second, third],
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        transfers(&res.messages),
+        vec![
+            (LP_A.to_string(), USER.to_string(), Uint128::new(150)),
+            (LP_B.to_string(), USER.to_string(), Uint128::new(70)),
+        ]
+    );
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::Locker { locker_id: first }).is_err());
+}
+
+fn event_attrs(res: &cosmwasm_std::Response, ty: &str) -> Vec<Vec<(String, String)>> {
+    res.events
+        .iter()
+        .filter(|event| event.ty == ty)
+        .map(|event| {
+            event.attributes.iter().map(|attr| (attr.key.clone(), attr.value.clone())).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn typed_events_describe_lock_extend_and_unlock() {
+    let mut deps = setup();
+    let env = mock_env();
+    let now = env.block.time.seconds();
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(LP_A, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary(&Cw20HookMsg::LockLP {
+                unlock_time: now + DAY,
+                metadata: None,
+                auto_relock: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let pairs = |items: &[(&str, String)]| -> Vec<(String, String)> {
+        items.iter().map(|(key, value)| (key.to_string(), value.clone())).collect()
+    };
+    assert_eq!(
+        event_attrs(&res, "lp_locker.locked"),
+        vec![pairs(&[
+            ("locker_id", "0".to_string()),
+            ("lp_token", LP_A.to_string()),
+            ("owner", USER.to_string()),
+            ("amount", "100".to_string()),
+            ("unlock_time", (now + DAY).to_string()),
+            ("auto_relock", String::new()),
+        ])]
+    );
+
+    let second = lock(&mut deps, USER, LP_A, 50, DAY);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::ExtendMany {
+            locker_ids: vec![0, second],
+            new_unlock_time: now + 2 * DAY,
+        },
+    )
+    .unwrap();
+    assert_eq!(event_attrs(&res, "lp_locker.extended").len(), 2);
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(2 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockMany {
+            locker_ids: vec![0, second],
+        },
+    )
+    .unwrap();
+    let unlocked = event_attrs(&res, "lp_locker.unlocked");
+    assert_eq!(unlocked.len(), 2);
+    assert!(unlocked[1].contains(&("amount".to_string(), "50".to_string())));
+    assert!(unlocked[1].contains(&("kind".to_string(), "matured".to_string())));
+}
+
+#[test]
+fn unlock_many_reports_failing_locker() {
+    let mut deps = setup();
+    let matured = lock(&mut deps, USER, LP_A, 100, DAY);
+    let locked = lock(&mut deps, USER, LP_A, 100, 10 * DAY);
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockMany {
+            locker_ids: vec![matured, locked],
+        },
+    )
+    .unwrap_err();
+
+    let unlock_time = mock_env().block.time.seconds() + 10 * DAY;
+    assert_eq!(
+        err,
+        ContractError::BatchFailed {
+            locker_id: locked,
+            reason: ContractError::StillLocked(unlock_time).to_string(),
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockMany {
+            locker_ids: vec![matured, matured],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DuplicateLocker(matured));
+}
+
+#[test]
+fn keepers_release_matured_lockers_for_a_tip() {
+    let mut deps = setup();
+    let update_config = |platform_fee_bps: u16, keeper_tip_bps: u16| ExecuteMsg::UpdateConfig {
+        admin: None,
+        reward_controller: None,
+        emergency_unlock_delay: None,
+        platform_fee_bps: Some(platform_fee_bps),
+        keeper_tip_bps: Some(keeper_tip_bps),
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_config(10, 50))
+        .unwrap_err();
+    assert_eq!(err, ContractError::KeeperTipTooHigh { max: 10 });
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_config(100, 50)).unwrap();
+
+    let matured = lock(&mut deps, USER, LP_A, 10_000, DAY);
+    let locked = lock(&mut deps, USER, LP_B, 1_000, 30 * DAY);
+    let opted_out = lock(&mut deps, "other", LP_A, 2_000, DAY);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other", &[]),
+        ExecuteMsg::SetKeeperRelease { enabled: false },
+    )
+    .unwrap();
+
+    let page = |deps: &MockDeps, owner: Option<&str>, limit: u32| -> UnlockableLockersResponse {
+        let msg = QueryMsg::UnlockableLockers {
+            owner: owner.map(str::to_string),
+            start_after: None,
+            limit: Some(limit),
+        };
+        from_json(query(deps.as_ref(), env_at(DAY), msg).unwrap()).unwrap()
+    };
+    let unlockable = |deps: &MockDeps, owner: Option<&str>| -> Vec<u64> {
+        let res = page(deps, owner, 10);
+        assert_eq!(res.start_after, None);
+        res.lockers.into_iter().map(|locker| locker.id).collect()
+    };
+    assert_eq!(unlockable(&deps, None), vec![matured]);
+    assert_eq!(unlockable(&deps, Some("other")), vec![opted_out]);
+    assert_eq!(unlockable(&deps, Some(USER)), vec![matured]);
+
+    // A full page hands back a cursor; the owner's still-locked LP_B locker is never listed
+    let unlock_time = mock_env().block.time.seconds() + DAY;
+    assert_eq!(page(&deps, Some(USER), 1).start_after, Some((unlock_time, matured)));
+
+    let release = |locker_ids: Vec<u64>| ExecuteMsg::ReleaseMatured { locker_ids };
+    for (locker_id, reason) in [
+        (opted_out, ContractError::KeeperReleaseDisabled {}),
+        (locked, ContractError::StillLocked(mock_env().block.time.seconds() + 30 * DAY)),
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            env_at(DAY),
+            mock_info("keeper", &[]),
+            release(vec![locker_id, matured]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BatchFailed {
+                locker_id,
+                reason: reason.to_string(),
+            }
+        );
+    }
+
+    // The contract holds 12_030 LP_A: 12_000 locked plus 30 of platform funds
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == LP_A => {
+            match from_json(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&BalanceResponse {
+                        balance: Uint128::new(12_030),
+                    })
+                    .unwrap(),
+                )),
+                _ => panic!("unexpected query"),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    // The owner gets the full amount; the 50 tip is capped at the 30 of platform funds
+    let res = execute(deps.as_mut(), env_at(DAY), mock_info("keeper", &[]), release(vec![matured]))
+        .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![
+            (LP_A.to_string(), USER.to_string(), Uint128::new(10_000)),
+            (LP_A.to_string(), "keeper".to_string(), Uint128::new(30)),
+        ]
+    );
+    let unlocked = event_attrs(&res, "lp_locker.unlocked");
+    assert!(unlocked[0].contains(&("kind".to_string(), "keeper".to_string())));
+    assert!(unlocked[0].contains(&("tip".to_string(), "30".to_string())));
+    assert!(unlockable(&deps, None).is_empty());
+
+    // Once opted back in, owners releasing their own lockers pay no tip
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other", &[]),
+        ExecuteMsg::SetKeeperRelease { enabled: true },
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env_at(DAY), mock_info("other", &[]), release(vec![opted_out]))
+        .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), "other".to_string(), Uint128::new(2_000))]
+    );
+}
+
+#[test]
+fn keeper_tips_never_use_lp_released_earlier_in_the_batch() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateConfig {
+            admin: None,
+            reward_controller: None,
+            emergency_unlock_delay: None,
+            platform_fee_bps: Some(100),
+            keeper_tip_bps: Some(50),
+        },
+    )
+    .unwrap();
+    let own = lock(&mut deps, "keeper", LP_A, 5_000, DAY);
+    let matured = lock(&mut deps, USER, LP_A, 10_000, DAY);
+
+    // The contract holds 15_030 LP_A: 15_000 locked plus 30 of platform funds
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == LP_A => {
+            match from_json(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&BalanceResponse {
+                        balance: Uint128::new(15_030),
+                    })
+                    .unwrap(),
+                )),
+                _ => panic!("unexpected query"),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    // The keeper's own 5_000 is not platform funds, so the tip stays capped at 30
+    let res = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ReleaseMatured {
+            locker_ids: vec![own, matured],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![
+            (LP_A.to_string(), "keeper".to_string(), Uint128::new(5_000)),
+            (LP_A.to_string(), USER.to_string(), Uint128::new(10_000)),
+            (LP_A.to_string(), "keeper".to_string(), Uint128::new(30)),
+        ]
+    );
+}
+
+#[test]
+fn extend_many_updates_every_locker() {
+    let mut deps = setup();
+    let first = lock(&mut deps, USER, LP_A, 100, DAY);
+    let second = lock(&mut deps, USER, LP_B, 100, 2 * DAY);
+    let new_unlock_time = mock_env().block.time.seconds() + 30 * DAY;
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::ExtendMany {
+            locker_ids: vec![first, second],
+            new_unlock_time,
+        },
+    )
+    .unwrap();
+
+    for locker_id in [first, second] {
+        let locker = query_locker(&deps, locker_id);
+        assert_eq!(locker.unlock_time, new_unlock_time);
+        assert_eq!(locker.extended_count, 1);
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("intruder", &[]),
+        ExecuteMsg::ExtendMany {
+            locker_ids: vec![first],
+            new_unlock_time: new_unlock_time + DAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchFailed {
+            locker_id: first,
+            reason: ContractError::NotOwner {}.to_string(),
+        }
+    );
+}
+
+#[test]
+fn operator_transfers_only_to_recipients_the_owner_named() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+    let approve = |recipients: Option<Vec<&str>>| ExecuteMsg::ApproveOperator {
+        operator: "wallet".to_string(),
+        permissions: vec![OperatorPermission::Transfer],
+        expires: None,
+        transfer_recipients: recipients
+            .map(|recipients| recipients.into_iter().map(str::to_string).collect()),
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), approve(None))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTransferRecipients {});
+    execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), approve(Some(vec!["cold"])))
+        .unwrap();
+
+    let transfer = |recipient: &str| ExecuteMsg::TransferLocker {
+        locker_id,
+        recipient: recipient.to_string(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("wallet", &[]), transfer("wallet"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::RecipientNotApproved {});
+
+    execute(deps.as_mut(), mock_env(), mock_info("wallet", &[]), transfer("cold")).unwrap();
+    assert_eq!(query_locker(&deps, locker_id).owner, Addr::unchecked("cold"));
+
+    // The approval was the previous owner's; it does not follow the locker
+    let err = execute(deps.as_mut(), mock_env(), mock_info("wallet", &[]), transfer("cold"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+}
+
+#[test]
+fn operator_can_extend_and_top_up_but_not_withdraw() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+    let now = mock_env().block.time.seconds();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::ApproveOperator {
+            operator: "bot".to_string(),
+            permissions: vec![
+                OperatorPermission::Extend,
+                OperatorPermission::Increase,
+                OperatorPermission::Extend,
+            ],
+            expires: Some(Expiration::AtTime(env_at(10 * DAY).block.time)),
+            transfer_recipients: None,
+        },
+    )
+    .unwrap();
+
+    let approval: OperatorResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Operator {
+                owner: USER.to_string(),
+                operator: "bot".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        approval.permissions,
+        vec![OperatorPermission::Extend, OperatorPermission::Increase]
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bot", &[]),
+        ExecuteMsg::ExtendLock {
+            locker_id,
+            new_unlock_time: now + 5 * DAY,
+        },
+    )
+    .unwrap();
+
+    // Operators may pay for a top-up, the locker stays with its owner
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(LP_A, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "bot".to_string(),
+            amount: Uint128::new(50),
+            msg: to_json_binary(&Cw20HookMsg::IncreaseLock { locker_id }).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let locker = query_locker(&deps, locker_id);
+    assert_eq!(locker.owner, Addr::unchecked(USER));
+    assert_eq!(locker.amount, Uint128::new(150));
+    assert_eq!(locker.unlock_time, now + 5 * DAY);
+
+    // Withdrawals and ungranted permissions stay with the owner
+    let err = execute(
+        deps.as_mut(),
+        env_at(6 * DAY),
+        mock_info("bot", &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bot", &[]),
+        ExecuteMsg::TransferLocker {
+            locker_id,
+            recipient: "bot".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+
+    // Expired approvals are ignored
+    let err = execute(
+        deps.as_mut(),
+        env_at(11 * DAY),
+        mock_info("bot", &[]),
+        ExecuteMsg::ExtendLock {
+            locker_id,
+            new_unlock_time: now + 20 * DAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RevokeOperator {
+            operator: "bot".to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bot", &[]),
+        ExecuteMsg::ExtendLock {
+            locker_id,
+            new_unlock_time: now + 6 * DAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+}
+
+#[test]
+fn increase_lock_tops_up_the_owners_locker() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    let top_up = |sender: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(50),
+            msg: to_json_binary(&Cw20HookMsg::IncreaseLock { locker_id }).unwrap(),
+        })
+    };
+
+    execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), top_up(USER)).unwrap();
+    assert_eq!(query_locker(&deps, locker_id).amount, Uint128::new(150));
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(LP_B, &[]), top_up(USER)).unwrap_err();
+    assert_eq!(err, ContractError::TokenMismatch {});
+
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), top_up("stranger")).unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+}
+
+#[test]
+fn transfer_locker_moves_balances_to_recipient() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, 30 * DAY);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateConfig {
+            admin: None,
+            reward_controller: Some("rewards".to_string()),
+            emergency_unlock_delay: None,
+            platform_fee_bps: None,
+            keeper_tip_bps: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::TransferLocker {
+            locker_id,
+            recipient: "heir".to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(query_locker(&deps, locker_id).owner, Addr::unchecked("heir"));
+
+    // The reward controller drops the stake registered by the previous owner
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "rewards".to_string(),
+            msg: to_json_binary(&RewardControllerExecuteMsg::LockerTransferred { locker_id })
+                .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let lockers: LockersResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LockersByOwner {
+                owner: "heir".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(lockers.lockers.len(), 1);
+}
+
+#[test]
+fn locker_history_records_every_change_and_survives_unlock() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, 30 * DAY);
+    let unlock_time = query_locker(&deps, locker_id).unlock_time;
+
+    let steps = [
+        (USER, ExecuteMsg::ExtendLock { locker_id, new_unlock_time: unlock_time + DAY }),
+        (USER, ExecuteMsg::RequestEmergencyUnlock { locker_id }),
+        (USER, ExecuteMsg::CancelEmergencyUnlock { locker_id }),
+        (USER, ExecuteMsg::RequestEmergencyUnlock { locker_id }),
+        (USER, ExecuteMsg::TransferLocker { locker_id, recipient: "heir".to_string() }),
+    ];
+    for (sender, msg) in steps {
+        execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg).unwrap();
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("heir", &[]),
+        ExecuteMsg::CancelEmergencyUnlock { locker_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmergencyNotRequested {});
+
+    execute(
+        deps.as_mut(),
+        env_at(40 * DAY),
+        mock_info("heir", &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap();
+
+    let history = |start_after: Option<u64>, limit: Option<u32>| -> LockerHistoryResponse {
+        let msg = QueryMsg::LockerHistory { locker_id, start_after, limit };
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    };
+
+    let execute_at = mock_env().block.time.seconds() + 3 * DAY;
+    let kinds: Vec<_> = history(None, None).events.into_iter().map(|event| event.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            LockerEventKind::Created {
+                owner: Addr::unchecked(USER),
+                amount: Uint128::new(100),
+                unlock_time,
+            },
+            LockerEventKind::Extended {
+                old_unlock_time: unlock_time,
+                new_unlock_time: unlock_time + DAY,
+            },
+            LockerEventKind::EmergencyRequested { execute_at },
+            LockerEventKind::EmergencyCancelled {},
+            LockerEventKind::EmergencyRequested { execute_at },
+            LockerEventKind::EmergencyCancelled {},
+            LockerEventKind::Transferred {
+                from: Addr::unchecked(USER),
+                to: Addr::unchecked("heir"),
+            },
+            LockerEventKind::Unlocked { amount: Uint128::new(100), emergency: false },
+        ]
+    );
+
+    let page = history(Some(5), Some(2)).events;
+    assert_eq!(page.iter().map(|event| event.id).collect::<Vec<_>>(), vec![6, 7]);
+    assert_eq!(page[1].time, env_at(40 * DAY).block.time.seconds());
+}
+
+#[test]
+fn owner_portfolio_tracks_totals_across_mutations() {
+    let mut deps = setup();
+    let short = lock(&mut deps, USER, LP_A, 100, DAY);
+    let long = lock(&mut deps, USER, LP_A, 200, 30 * DAY);
+    let pending = lock(&mut deps, USER, LP_B, 50, 60 * DAY);
+    let moved = lock(&mut deps, USER, LP_B, 70, 60 * DAY);
+
+    let steps = [
+        ExecuteMsg::RequestEmergencyUnlock { locker_id: pending },
+        ExecuteMsg::RequestEmergencyUnlock { locker_id: moved },
+        ExecuteMsg::TransferLocker { locker_id: moved, recipient: "heir".to_string() },
+    ];
+    for msg in steps {
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg).unwrap();
+    }
+
+    let portfolio = |deps: &MockDeps, owner: &str, env: Env| -> OwnerPortfolioResponse {
+        let msg = QueryMsg::OwnerPortfolio {
+            owner: owner.to_string(),
+            start_after: None,
+            limit: None,
+        };
+        from_json(query(deps.as_ref(), env, msg).unwrap()).unwrap()
+    };
+
+    let now = mock_env().block.time.seconds();
+    let res = portfolio(&deps, USER, env_at(2 * DAY));
+    assert_eq!(res.lockers, 3);
+    assert_eq!(res.next_unlock_time, Some(now + 30 * DAY));
+    assert_eq!(
+        res.entries,
+        vec![
+            PortfolioEntry {
+                lp_token: Addr::unchecked(LP_A),
+                amount: Uint128::new(300),
+                lockers: 2,
+                next_unlock_time: Some(now + 30 * DAY),
+                unlockable: Uint128::new(100),
+                unlockable_capped: false,
+                emergency_pending: Uint128::zero(),
+            },
+            PortfolioEntry {
+                lp_token: Addr::unchecked(LP_B),
+                amount: Uint128::new(50),
+                lockers: 1,
+                next_unlock_time: Some(now + 60 * DAY),
+                unlockable: Uint128::zero(),
+                unlockable_capped: false,
+                emergency_pending: Uint128::new(50),
+            },
+        ]
+    );
+
+    // The transfer dropped the previous owner's emergency request
+    let res = portfolio(&deps, "heir", mock_env());
+    assert_eq!(res.entries[0].amount, Uint128::new(70));
+    assert_eq!(res.entries[0].emergency_pending, Uint128::zero());
+    assert_eq!(res.entries[0].next_unlock_time, Some(now + 60 * DAY));
+
+    execute(
+        deps.as_mut(),
+        env_at(2 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id: short },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::CancelEmergencyUnlock { locker_id: pending },
+    )
+    .unwrap();
+
+    let res = portfolio(&deps, USER, env_at(2 * DAY));
+    assert_eq!(res.lockers, 2);
+    assert_eq!(res.entries[0].amount, Uint128::new(200));
+    assert_eq!(res.entries[0].unlockable, Uint128::zero());
+    assert_eq!(res.entries[1].emergency_pending, Uint128::zero());
+
+    execute(
+        deps.as_mut(),
+        env_at(30 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id: long },
+    )
+    .unwrap();
+    let res = portfolio(&deps, USER, env_at(30 * DAY));
+    assert_eq!(res.entries.len(), 1);
+    assert_eq!(res.entries[0].lp_token, Addr::unchecked(LP_B));
+}
+
+#[test]
+fn owner_portfolio_pages_by_lp_and_caps_the_matured_scan() {
+    let mut deps = setup();
+    for _ in 0..101 {
+        lock(&mut deps, USER, LP_A, 10, DAY);
+    }
+    lock(&mut deps, USER, LP_B, 10, 30 * DAY);
+
+    let portfolio = |start_after: Option<&str>, limit: u32| -> OwnerPortfolioResponse {
+        let msg = QueryMsg::OwnerPortfolio {
+            owner: USER.to_string(),
+            start_after: start_after.map(str::to_string),
+            limit: Some(limit),
+        };
+        from_json(query(deps.as_ref(), env_at(DAY), msg).unwrap()).unwrap()
+    };
+
+    // The scan stops after 100 matured lockers and ends the page there
+    let first = portfolio(None, 10);
+    assert_eq!(first.lockers, 102);
+    assert_eq!(first.next_unlock_time, Some(mock_env().block.time.seconds() + 30 * DAY));
+    assert_eq!(first.entries.len(), 1);
+    assert_eq!(first.entries[0].unlockable, Uint128::new(1_000));
+    assert!(first.entries[0].unlockable_capped);
+    assert_eq!(first.start_after, Some(Addr::unchecked(LP_A)));
+
+    let second = portfolio(Some(LP_A), 10);
+    assert_eq!(second.entries[0].lp_token, Addr::unchecked(LP_B));
+    assert!(!second.entries[0].unlockable_capped);
+    assert_eq!(second.start_after, None);
+}
+
+#[test]
+fn locker_metadata_is_validated_and_owner_editable() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    let update = |metadata: LockerMetadata| ExecuteMsg::UpdateLockerMetadata {
+        locker_id,
+        metadata: Some(metadata),
+    };
+    let metadata = LockerMetadata {
+        project_name: Some("Paxi Swap".to_string()),
+        website: Some("https://paxi.network".to_string()),
+        logo_uri: Some("ipfs://bafy/logo.png".to_string()),
+        description: Some("Team liquidity".to_string()),
+        tags: vec!["team".to_string()],
+    };
+
+    execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(metadata.clone())).unwrap();
+    assert_eq!(query_locker(&deps, locker_id).metadata, Some(metadata.clone()));
+
+    let intruder = mock_info("intruder", &[]);
+    let err = execute(deps.as_mut(), mock_env(), intruder, update(metadata.clone())).unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+
+    let bad_url = LockerMetadata {
+        website: Some("javascript:alert(1)".to_string()),
+        ..metadata.clone()
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(bad_url))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidMetadataUrl {
+            field: "website".to_string(),
+        }
+    );
+
+    let too_long = LockerMetadata {
+        project_name: Some("x".repeat(65)),
+        ..metadata.clone()
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(too_long))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MetadataTooLong {
+            field: "project_name".to_string(),
+            max: 64,
+        }
+    );
+
+    let too_many_tags = LockerMetadata {
+        tags: vec!["tag".to_string(); 11],
+        ..metadata
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(too_many_tags))
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyTags { max: 10 });
+}
+
+#[test]
+fn lock_and_extend_by_duration_resolve_against_block_time() {
+    let mut deps = setup();
+    let lock_for = |lock_duration: u64| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary(&Cw20HookMsg::LockLPFor {
+                lock_duration,
+                metadata: None,
+                auto_relock: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    // Inclusion an hour after signing still locks for exactly the minimum
+    let included = env_at(3_600);
+    execute(deps.as_mut(), included.clone(), mock_info(LP_A, &[]), lock_for(DAY)).unwrap();
+    let locker = query_locker(&deps, 0);
+    assert_eq!(locker.unlock_time, included.block.time.seconds() + DAY);
+
+    let err = execute(deps.as_mut(), included.clone(), mock_info(LP_A, &[]), lock_for(DAY - 1))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnlockTime { min: DAY, max: 365 * DAY });
+
+    let extend_by = |additional_seconds: u64| ExecuteMsg::ExtendLockBy {
+        locker_id: 0,
+        additional_seconds,
+    };
+    execute(deps.as_mut(), included.clone(), mock_info(USER, &[]), extend_by(DAY)).unwrap();
+    assert_eq!(query_locker(&deps, 0).unlock_time, locker.unlock_time + DAY);
+
+    // A matured locker is extended from the current time
+    let later = env_at(10 * DAY);
+    execute(deps.as_mut(), later.clone(), mock_info(USER, &[]), extend_by(DAY)).unwrap();
+    assert_eq!(query_locker(&deps, 0).unlock_time, later.block.time.seconds() + DAY);
+
+    let err = execute(deps.as_mut(), later, mock_info(USER, &[]), extend_by(365 * DAY))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnlockTime { min: DAY, max: 365 * DAY });
+}
+
+#[test]
+fn auto_relock_keeps_locker_locked_until_disabled() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateConfig {
+            admin: None,
+            reward_controller: Some("rewards".to_string()),
+            emergency_unlock_delay: None,
+            platform_fee_bps: None,
+            keeper_tip_bps: None,
+        },
+    )
+    .unwrap();
+    let notified = |res: &cosmwasm_std::Response, msg: RewardControllerExecuteMsg| {
+        res.messages.iter().any(|sub| {
+            sub.msg
+                == CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "rewards".to_string(),
+                    msg: to_json_binary(&msg).unwrap(),
+                    funds: vec![],
+                })
+        })
+    };
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::EnableAutoRelock {
+            locker_id,
+            duration: 30 * DAY,
+        },
+    )
+    .unwrap();
+
+    // Long after the original unlock time the locker is still rolling
+    let later = env_at(100 * DAY);
+    let err = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AutoRelockEnabled {});
+
+    // The reward controller re-reads the stake once the countdown starts
+    let res = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(USER, &[]),
+        ExecuteMsg::DisableAutoRelock { locker_id },
+    )
+    .unwrap();
+    assert!(notified(&res, RewardControllerExecuteMsg::LockerUpdated { locker_id }));
+
+    let locker = query_locker(&deps, locker_id);
+    assert_eq!(locker.auto_relock, None);
+    assert_eq!(locker.unlock_time, later.block.time.seconds() + 30 * DAY);
+
+    // and drops it once the LP is withdrawn
+    let res = execute(
+        deps.as_mut(),
+        env_at(130 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap();
+    let closed = RewardControllerExecuteMsg::LockersClosed {
+        locker_ids: vec![locker_id],
+    };
+    assert!(notified(&res, closed));
+}
+
+#[test]
+fn auto_relock_duration_respects_whitelist_bounds() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::EnableAutoRelock {
+            locker_id,
+            duration: 400 * DAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUnlockTime {
+            min: DAY,
+            max: 365 * DAY,
+        }
+    );
+}
+
+#[test]
+fn disabled_lp_rejects_new_locks_but_keeps_lockers_working() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::DisableLP {
+            lp_token: LP_A.to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(LP_A, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary(&Cw20HookMsg::LockLP {
+                unlock_time: mock_env().block.time.seconds() + DAY,
+                metadata: None,
+                auto_relock: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LPNotWhitelisted {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::ExtendLock {
+            locker_id,
+            new_unlock_time: mock_env().block.time.seconds() + 2 * DAY,
+        },
+    )
+    .unwrap();
+
+    // The entry cannot be deleted while lockers depend on it
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RemoveLP {
+            lp_token: LP_A.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LPHasActiveLocks {});
+
+    execute(
+        deps.as_mut(),
+        env_at(2 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap();
+}
+
+#[test]
+fn update_lp_validates_durations() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateLP {
+            lp_token: LP_A.to_string(),
+            min_lock_duration: Some(400 * DAY),
+            max_lock_duration: None,
+            bonus_multiplier: None,
+            tiers: None,
+            enabled: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLockDurations {
+            min: 400 * DAY,
+            max: 365 * DAY,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::WhitelistLP {
+            lp_token: LP_A.to_string(),
+            min_lock_duration: DAY,
+            max_lock_duration: DAY,
+            bonus_multiplier: Decimal::one(),
+            tiers: None,
+            pair_contract: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LPAlreadyWhitelisted {});
+}
+
+#[test]
+fn lock_tiers_resolve_at_lock_extend_and_relock() {
+    let mut deps = setup();
+    let tier = |days: u64, percent: u64| LockTier {
+        min_duration: days * DAY,
+        multiplier: Decimal::percent(percent),
+    };
+    let update_tiers = |tiers: Vec<LockTier>| ExecuteMsg::UpdateLP {
+        lp_token: LP_A.to_string(),
+        min_lock_duration: None,
+        max_lock_duration: None,
+        bonus_multiplier: None,
+        tiers: Some(tiers),
+        enabled: None,
+    };
+
+    for invalid in [
+        vec![tier(90, 150), tier(30, 120)],
+        vec![tier(30, 150), tier(90, 120)],
+        vec![tier(400, 150)],
+    ] {
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_tiers(invalid))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidTiers { max: 10 });
+    }
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        update_tiers(vec![tier(30, 120), tier(90, 150)]),
+    )
+    .unwrap();
+
+    let tier_for = |deps: &MockDeps, duration: u64| -> Decimal {
+        let msg = QueryMsg::TierFor { lp_token: LP_A.to_string(), duration };
+        let res: TierForResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap())
+            .unwrap();
+        res.multiplier
+    };
+    assert_eq!(tier_for(&deps, 29 * DAY), Decimal::one());
+    assert_eq!(tier_for(&deps, 30 * DAY), Decimal::percent(120));
+    assert_eq!(tier_for(&deps, 365 * DAY), Decimal::percent(150));
+
+    let short = lock(&mut deps, USER, LP_A, 100, 10 * DAY);
+    let medium = lock(&mut deps, USER, LP_A, 100, 60 * DAY);
+    assert_eq!(query_locker(&deps, short).bonus_multiplier, Decimal::one());
+    assert_eq!(query_locker(&deps, medium).bonus_multiplier, Decimal::percent(120));
+
+    let now = mock_env().block.time.seconds();
+    let steps = [
+        ExecuteMsg::ExtendLock { locker_id: short, new_unlock_time: now + 100 * DAY },
+        ExecuteMsg::EnableAutoRelock { locker_id: medium, duration: 90 * DAY },
+    ];
+    for msg in steps {
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg).unwrap();
+    }
+    assert_eq!(query_locker(&deps, short).bonus_multiplier, Decimal::percent(150));
+    assert_eq!(query_locker(&deps, medium).bonus_multiplier, Decimal::percent(150));
+
+    // Changing tiers leaves existing lockers untouched
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_tiers(vec![])).unwrap();
+    assert_eq!(query_locker(&deps, short).bonus_multiplier, Decimal::percent(150));
+    assert_eq!(tier_for(&deps, 365 * DAY), Decimal::one());
+}
+
+#[test]
+fn lock_limits_apply_per_lp_over_defaults() {
+    let mut deps = setup();
+    let set_limits = |lp_token: Option<&str>, limits: LockLimits| ExecuteMsg::SetLockLimits {
+        lp_token: lp_token.map(str::to_string),
+        limits,
+    };
+
+    let defaults = LockLimits {
+        min_lock_amount: Some(Uint128::new(100)),
+        max_total_locked: None,
+        max_lockers_per_owner: Some(2),
+    };
+    let lp_a = LockLimits {
+        min_lock_amount: None,
+        max_total_locked: Some(Uint128::new(1_000)),
+        max_lockers_per_owner: Some(1),
+    };
+    let msg = set_limits(None, defaults.clone());
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    for msg in [set_limits(None, defaults), set_limits(Some(LP_A), lp_a)] {
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+    }
+
+    let lock_msg = |amount: u128, locker_id: Option<u64>| {
+        let msg = match locker_id {
+            Some(locker_id) => Cw20HookMsg::IncreaseLock { locker_id },
+            None => Cw20HookMsg::LockLPFor {
+                lock_duration: DAY,
+                metadata: None,
+                auto_relock: None,
+            },
+        };
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&msg).unwrap(),
+        })
+    };
+
+    // The default minimum still applies where LP_A sets none
+    let err = execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), lock_msg(99, None))
+        .unwrap_err();
+    assert_eq!(err, ContractError::BelowMinLockAmount { min: Uint128::new(100) });
+
+    execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), lock_msg(900, None)).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), lock_msg(100, None))
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyLockers { max: 1 });
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), lock_msg(101, Some(0)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::LockCapExceeded { cap: Uint128::new(1_000) });
+    execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), lock_msg(100, Some(0))).unwrap();
+
+    // The default cap counts lockers of every LP; transfers count against the recipient
+    lock(&mut deps, USER, LP_B, 100, DAY);
+    let err = execute(deps.as_mut(), mock_env(), mock_info(LP_B, &[]), lock_msg(100, None))
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyOwnerLockers { max: 2 });
+    let third = lock(&mut deps, "other", LP_B, 100, DAY);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other", &[]),
+        ExecuteMsg::TransferLocker { locker_id: third, recipient: USER.to_string() },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TooManyOwnerLockers { max: 2 });
+
+    let msg = QueryMsg::OwnerPortfolio {
+        owner: USER.to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res: OwnerPortfolioResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap())
+        .unwrap();
+    assert_eq!(res.lockers, 2);
+}
+
+/// Serves a factory with one pair of LP_C, and a fake pair claiming the same LP token.
+/// The pair holds 5000 upaxi and 20000 token against 1000 LP_C.
+fn mock_dex(deps: &mut MockDeps) {
+    mock_dex_with_supply(deps, 1000);
+}
+
+/// `mock_dex` with the given LP_C total supply
+fn mock_dex_with_supply(deps: &mut MockDeps, total_supply: u128) {
+    deps.querier.update_wasm(move |query| {
+        let pair_info = |contract_addr: &str| PairInfo {
+            asset_infos: vec![
+                AssetInfo::Native("upaxi".to_string()),
+                AssetInfo::Cw20(Addr::unchecked("token")),
+            ],
+            contract_addr: Addr::unchecked(contract_addr),
+            liquidity_token: Addr::unchecked(LP_C),
+        };
+        let response = match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "factory" => {
+                let FactoryQueryMsg::Pair { .. } = from_json(msg).unwrap();
+                to_json_binary(&pair_info("pair"))
+            }
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == LP_C => {
+                match from_json(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => to_json_binary(&TokenInfoResponse {
+                        name: "LP".to_string(),
+                        symbol: "LP".to_string(),
+                        decimals: 6,
+                        total_supply: Uint128::new(total_supply),
+                    }),
+                    _ => panic!("unexpected query"),
+                }
+            }
+            WasmQuery::Smart { contract_addr, msg } => match from_json(msg).unwrap() {
+                PairQueryMsg::Pair {} => to_json_binary(&pair_info(contract_addr)),
+                PairQueryMsg::Pool {} => to_json_binary(&PoolResponse {
+                    assets: vec![
+                        Asset {
+                            info: AssetInfo::Native("upaxi".to_string()),
+                            amount: Uint128::new(5000),
+                        },
+                        Asset {
+                            info: AssetInfo::Cw20(Addr::unchecked("token")),
+                            amount: Uint128::new(20000),
+                        },
+                    ],
+                }),
+            },
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+}
+
+/// Trusts the `mock_dex` factory and whitelists LP_C with its verified pair
+fn whitelist_pair_lp(deps: &mut MockDeps) {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::AddTrustedFactory {
+            factory: "factory".to_string(),
+            permissionless: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::WhitelistLP {
+            lp_token: LP_C.to_string(),
+            min_lock_duration: DAY,
+            max_lock_duration: 30 * DAY,
+            bonus_multiplier: Decimal::one(),
+            tiers: None,
+            pair_contract: Some("pair".to_string()),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn whitelist_verifies_lp_against_trusted_factory() {
+    let mut deps = setup();
+    mock_dex(&mut deps);
+
+    let whitelist = |pair_contract: &str| ExecuteMsg::WhitelistLP {
+        lp_token: LP_C.to_string(),
+        min_lock_duration: DAY,
+        max_lock_duration: 30 * DAY,
+        bonus_multiplier: Decimal::one(),
+        tiers: None,
+        pair_contract: Some(pair_contract.to_string()),
+    };
+
+    // Nothing is verifiable before a factory is trusted
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), whitelist("pair"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnverifiedLP {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::AddTrustedFactory {
+            factory: "factory".to_string(),
+            permissionless: None,
+        },
+    )
+    .unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), whitelist("fake_pair"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnverifiedLP {});
+
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), whitelist("pair")).unwrap();
+
+    let res: WhitelistedLPResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::WhitelistedLP {
+                lp_token: LP_C.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let pair = res.pair.unwrap();
+    assert_eq!(pair.factory, Addr::unchecked("factory"));
+    assert_eq!(pair.pair_contract, Addr::unchecked("pair"));
+    assert_eq!(pair.asset_infos.len(), 2);
+}
+
+#[test]
+fn locked_value_splits_reserves_by_share_of_supply() {
+    let mut deps = setup();
+    mock_dex(&mut deps);
+    whitelist_pair_lp(&mut deps);
+
+    // 100 of 1000 LP is worth a tenth of each reserve
+    let first = lock(&mut deps, USER, LP_C, 100, DAY);
+    lock(&mut deps, USER, LP_C, 150, DAY);
+
+    let value: LockedValueResponse = from_json(
+        query(deps.as_ref(), mock_env(), QueryMsg::LockerValue { locker_id: first }).unwrap(),
+    )
+    .unwrap();
+    let amounts: Vec<u128> = value.assets.iter().map(|asset| asset.amount.u128()).collect();
+    assert_eq!(amounts, vec![500, 2000]);
+
+    let value: LockedValueResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LockedValueByLP {
+                lp_token: LP_C.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(value.amount, Uint128::new(250));
+    let amounts: Vec<u128> = value.assets.iter().map(|asset| asset.amount.u128()).collect();
+    assert_eq!(amounts, vec![1250, 5000]);
+}
+
+#[test]
+fn locked_value_rejects_missing_pair_and_zeroes_empty_pool() {
+    let mut deps = setup();
+    mock_dex(&mut deps);
+    whitelist_pair_lp(&mut deps);
+    let paired = lock(&mut deps, USER, LP_C, 100, DAY);
+    let unpaired = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    // LPs without a verified pair cannot be valued
+    let queries = [
+        QueryMsg::LockerValue { locker_id: unpaired },
+        QueryMsg::LockedValueByLP {
+            lp_token: LP_A.to_string(),
+        },
+    ];
+    for msg in queries {
+        let err = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+        assert!(err.to_string().contains("LP token has no verified pair"));
+    }
+
+    // A pool whose LP supply is gone is worth nothing instead of dividing by zero
+    mock_dex_with_supply(&mut deps, 0);
+    let value: LockedValueResponse = from_json(
+        query(deps.as_ref(), mock_env(), QueryMsg::LockerValue { locker_id: paired }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(value.amount, Uint128::new(100));
+    assert!(value.assets.iter().all(|asset| asset.amount.is_zero()));
+    assert_eq!(value.assets.len(), 2);
+}
+
+#[test]
+fn unlock_and_withdraw_liquidity_requires_the_verified_pair() {
+    let mut deps = setup();
+    mock_dex(&mut deps);
+    whitelist_pair_lp(&mut deps);
+    let locker_id = lock(&mut deps, USER, LP_C, 100, DAY);
+    let unverified = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    let unlock = |locker_id: u64, pair_contract: &str| ExecuteMsg::UnlockAndWithdrawLiquidity {
+        locker_id,
+        pair_contract: pair_contract.to_string(),
+        min_assets: vec![],
+    };
+
+    // LPs without a verified pair cannot be sent to any pair
+    let err = execute(deps.as_mut(), env_at(DAY), mock_info(USER, &[]), unlock(unverified, "pair"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnverifiedLP {});
+
+    let err = execute(deps.as_mut(), env_at(DAY), mock_info(USER, &[]), unlock(locker_id, "fake"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::PairMismatch {});
+    assert_eq!(query_locker(&deps, locker_id).amount, Uint128::new(100));
+
+    let res = execute(deps.as_mut(), env_at(DAY), mock_info(USER, &[]), unlock(locker_id, "pair"))
+        .unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_json(msg).unwrap() {
+            Cw20ExecuteMsg::Send { contract, .. } => assert_eq!(contract, "pair"),
+            other => panic!("unexpected message {other:?}"),
+        },
+        other => panic!("unexpected message {other:?}"),
+    }
+}
+
+#[test]
+fn trusted_factory_lps_can_be_whitelisted_permissionlessly() {
+    let mut deps = setup();
+    mock_dex(&mut deps);
+
+    let from_factory = |pair_contract: &str| ExecuteMsg::WhitelistLPFromFactory {
+        pair_contract: pair_contract.to_string(),
+    };
+    let add_factory = |permissionless: Option<LPDefaults>| ExecuteMsg::AddTrustedFactory {
+        factory: "factory".to_string(),
+        permissionless,
+    };
+
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_factory(None)).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), from_factory("pair"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::PermissionlessDisabled {});
+
+    let defaults = LPDefaults {
+        min_lock_duration: 7 * DAY,
+        max_lock_duration: 90 * DAY,
+        bonus_multiplier: Decimal::percent(120),
+        tiers: vec![],
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), add_factory(Some(defaults)))
+        .unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), from_factory("fake_pair"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnverifiedLP {});
+
+    execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), from_factory("pair")).unwrap();
+    lock(&mut deps, USER, LP_C, 100, 7 * DAY);
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), from_factory("pair"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::LPAlreadyWhitelisted {});
+}
+
+#[test]
+fn paused_lp_blocks_locks_but_not_unlock() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+    lock(&mut deps, USER, LP_B, 100, DAY);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetLPPaused {
+            lp_token: LP_A.to_string(),
+            paused: true,
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RequestEmergencyUnlock { locker_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LPPaused {});
+
+    // Other LP tokens keep working
+    lock(&mut deps, USER, LP_B, 100, DAY);
+
+    execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap();
+}
+
+#[test]
+fn pause_flags_block_individual_operations() {
+    let mut deps = setup();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetPauseFlags {
+            locks: None,
+            extends: Some(true),
+            emergency_requests: None,
+            transfers: Some(true),
+            fee_withdrawal: None,
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::ExtendLock {
+            locker_id,
+            new_unlock_time: mock_env().block.time.seconds() + 2 * DAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::TransferLocker {
+            locker_id,
+            recipient: "heir".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    // Locking is unaffected by the extend and transfer switches
+    lock(&mut deps, USER, LP_A, 100, DAY);
+}
+
+#[test]
+fn recover_tokens_only_moves_surplus() {
+    let mut deps = setup();
+    lock(&mut deps, USER, LP_A, 100, DAY);
+
+    // The contract holds 150 LP_A: 100 locked plus 50 sent with a plain transfer
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == LP_A => {
+            match from_json(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&BalanceResponse {
+                        balance: Uint128::new(150),
+                    })
+                    .unwrap(),
+                )),
+                _ => panic!("unexpected query"),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let recover = |amount: u128| ExecuteMsg::RecoverTokens {
+        asset: AssetInfo::Cw20(Addr::unchecked(LP_A)),
+        amount: Uint128::new(amount),
+        recipient: "treasury".to_string(),
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), recover(51)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientSurplus {
+            available: Uint128::new(50),
+        }
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), recover(50)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let pause_fee_withdrawal = |paused: bool| ExecuteMsg::SetPauseFlags {
+        locks: None,
+        extends: None,
+        emergency_requests: None,
+        transfers: None,
+        fee_withdrawal: Some(paused),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), pause_fee_withdrawal(true))
+        .unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), recover(50)).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), pause_fee_withdrawal(false))
+        .unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), recover(50)).unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), "treasury".to_string(), Uint128::new(50))]
+    );
+}
+
+#[test]
+fn blocked_owners_cannot_lock_or_withdraw_but_lockers_are_recoverable() {
+    let mut deps = setup();
+    let matured = lock(&mut deps, USER, LP_A, 100, DAY);
+    let pending = lock(&mut deps, USER, LP_B, 70, 10 * DAY);
+
+    // "sanctioned" is only known to the external screening contract
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "screening" => {
+            let ScreeningQueryMsg::IsBlocked { address } = from_json(msg).unwrap();
+            let response = ScreeningResponse {
+                blocked: address == "sanctioned",
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetScreening {
+            contract: Some("screening".to_string()),
+            recovery_address: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateBlocklist {
+            add: vec![USER.to_string()],
+            remove: vec![],
+        },
+    )
+    .unwrap();
+
+    let lock_msg = |owner: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: owner.to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary(&Cw20HookMsg::LockLPFor {
+                lock_duration: DAY,
+                metadata: None,
+                auto_relock: None,
+            })
+            .unwrap(),
+        })
+    };
+    for owner in [USER, "sanctioned"] {
+        let err = execute(deps.as_mut(), mock_env(), mock_info(LP_A, &[]), lock_msg(owner))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Blocked { address: owner.to_string() });
     }
+    lock(&mut deps, "other", LP_A, 100, DAY);
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id: matured },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Blocked { address: USER.to_string() });
+
+    let recover = |locker_ids: Vec<u64>| ExecuteMsg::RecoverBlockedLockers { locker_ids };
+    let err = execute(deps.as_mut(), env_at(DAY), mock_info(ADMIN, &[]), recover(vec![matured]))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NoRecoveryAddress {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetScreening {
+            contract: Some("screening".to_string()),
+            recovery_address: Some("recovery".to_string()),
+        },
+    )
+    .unwrap();
+
+    // Recovery still honours the unlock time
+    let err = execute(deps.as_mut(), env_at(DAY), mock_info(ADMIN, &[]), recover(vec![pending]))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchFailed {
+            locker_id: pending,
+            reason: ContractError::StillLocked(mock_env().block.time.seconds() + 10 * DAY)
+                .to_string(),
+        }
+    );
+
+    let res = execute(deps.as_mut(), env_at(DAY), mock_info(ADMIN, &[]), recover(vec![matured]))
+        .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), "recovery".to_string(), Uint128::new(100))]
+    );
+    let unlocked = event_attrs(&res, "lp_locker.unlocked");
+    assert!(unlocked[0].contains(&("owner".to_string(), USER.to_string())));
+    assert!(unlocked[0].contains(&("recipient".to_string(), "recovery".to_string())));
+
+    let blocked: ScreeningResponse = from_json(
+        query(deps.as_ref(), mock_env(), QueryMsg::IsBlocked { address: USER.to_string() })
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(blocked.blocked);
+}
+
+#[test]
+fn recovery_writes_off_receipts_of_blocked_owners() {
+    let mut deps = setup();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "receipt" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&Some(MinterResponse {
+                    minter: mock_env().contract.address.to_string(),
+                    cap: None,
+                }))
+                .unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetReceiptToken {
+            lp_token: LP_A.to_string(),
+            receipt_token: Some("receipt".to_string()),
+        },
+    )
+    .unwrap();
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetScreening {
+            contract: None,
+            recovery_address: Some("recovery".to_string()),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateBlocklist {
+            add: vec![USER.to_string()],
+            remove: vec![],
+        },
+    )
+    .unwrap();
+
+    // The blocked owner cannot redeem the receipts, so recovery does not ask for them
+    let res = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecoverBlockedLockers {
+            locker_ids: vec![locker_id],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), "recovery".to_string(), Uint128::new(100))]
+    );
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::Locker { locker_id }).is_err());
+}
+
+#[test]
+fn beneficiary_claims_after_owner_inactivity() {
+    let mut deps = setup();
+    let first = lock(&mut deps, USER, LP_A, 100, DAY);
+    let second = lock(&mut deps, USER, LP_B, 70, DAY);
+    let now = mock_env().block.time.seconds();
+
+    let set_beneficiary = |locker_id: Option<u64>, beneficiary: &str, period: u64| {
+        ExecuteMsg::SetBeneficiary {
+            locker_id,
+            beneficiary: beneficiary.to_string(),
+            inactivity_period: period,
+        }
+    };
+    let info = mock_info(USER, &[]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), set_beneficiary(None, "heir", DAY))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidInactivityPeriod {
+            min: 30 * DAY,
+            max: 5 * 365 * DAY,
+        }
+    );
+    execute(deps.as_mut(), mock_env(), info, set_beneficiary(None, "heir", 30 * DAY)).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other", &[]),
+        set_beneficiary(Some(second), "other", 30 * DAY),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        set_beneficiary(Some(second), "partner", 60 * DAY),
+    )
+    .unwrap();
+
+    let beneficiary = |deps: &MockDeps, locker_id: u64| -> BeneficiaryResponse {
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Beneficiary { locker_id }).unwrap())
+            .unwrap()
+    };
+    assert_eq!(beneficiary(&deps, first).claimable_at, Some(now + 31 * DAY));
+    let per_locker = beneficiary(&deps, second).beneficiary.unwrap();
+    assert_eq!(per_locker.address, Addr::unchecked("partner"));
+
+    // A heartbeat after the unlock time restarts the inactivity period
+    execute(deps.as_mut(), env_at(11 * DAY), mock_info(USER, &[]), ExecuteMsg::Heartbeat {})
+        .unwrap();
+    assert_eq!(beneficiary(&deps, first).claimable_at, Some(now + 41 * DAY));
+
+    let claim = ExecuteMsg::ClaimInheritance { locker_id: first };
+    let err = execute(deps.as_mut(), env_at(41 * DAY), mock_info("partner", &[]), claim.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(deps.as_mut(), env_at(40 * DAY), mock_info("heir", &[]), claim.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::OwnerStillActive(now + 41 * DAY));
+
+    // The locker passes to the heir, who then unlocks it as its owner
+    let res = execute(deps.as_mut(), env_at(41 * DAY), mock_info("heir", &[]), claim).unwrap();
+    assert!(transfers(&res.messages).is_empty());
+    let inherited = event_attrs(&res, "lp_locker.inherited");
+    assert!(inherited[0].contains(&("from".to_string(), USER.to_string())));
+    assert!(inherited[0].contains(&("to".to_string(), "heir".to_string())));
+    assert_eq!(query_locker(&deps, first).owner, Addr::unchecked("heir"));
+
+    let msg = QueryMsg::LockerHistory { locker_id: first, start_after: None, limit: None };
+    let history: LockerHistoryResponse =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        history.events.last().unwrap().kind,
+        LockerEventKind::Inherited {
+            from: Addr::unchecked(USER),
+            to: Addr::unchecked("heir"),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(41 * DAY),
+        mock_info("heir", &[]),
+        ExecuteMsg::UnlockLP { locker_id: first },
+    )
+    .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), "heir".to_string(), Uint128::new(100))]
+    );
+}
+
+#[test]
+fn beneficiary_inherits_auto_relock_and_receipt_backed_lockers() {
+    let mut deps = setup();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "receipt" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&Some(MinterResponse {
+                    minter: mock_env().contract.address.to_string(),
+                    cap: None,
+                }))
+                .unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetReceiptToken {
+            lp_token: LP_A.to_string(),
+            receipt_token: Some("receipt".to_string()),
+        },
+    )
+    .unwrap();
+
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+    for msg in [
+        ExecuteMsg::EnableAutoRelock { locker_id, duration: 30 * DAY },
+        ExecuteMsg::SetBeneficiary {
+            locker_id: Some(locker_id),
+            beneficiary: "heir".to_string(),
+            inactivity_period: 30 * DAY,
+        },
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), msg).unwrap();
+    }
+
+    // The rolling lock carries over, and the heir gets receipts to redeem it later
+    let res = execute(
+        deps.as_mut(),
+        env_at(31 * DAY),
+        mock_info("heir", &[]),
+        ExecuteMsg::ClaimInheritance { locker_id },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "receipt".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: "heir".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    let locker = query_locker(&deps, locker_id);
+    assert_eq!(locker.owner, Addr::unchecked("heir"));
+    assert_eq!(locker.auto_relock, Some(30 * DAY));
+}
+
+#[test]
+fn receipt_tokens_are_minted_on_lock_and_burned_on_unlock() {
+    let mut deps = setup();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "receipt" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&Some(MinterResponse {
+                    minter: mock_env().contract.address.to_string(),
+                    cap: None,
+                }))
+                .unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetReceiptToken {
+            lp_token: LP_A.to_string(),
+            receipt_token: Some("receipt".to_string()),
+        },
+    )
+    .unwrap();
+
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(LP_A, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary(&Cw20HookMsg::LockLP {
+                unlock_time: env.block.time.seconds() + DAY,
+                metadata: None,
+                auto_relock: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "receipt".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: USER.to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ReceiptRequired {});
+
+    // Governance cannot release LP that still backs circulating receipts
+    let err = sudo(deps.as_mut(), mock_env(), SudoMsg::ForceRelease { locker_ids: vec![0] })
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchFailed {
+            locker_id: 0,
+            reason: ContractError::ReceiptRequired {}.to_string(),
+        }
+    );
+
+    let redeem = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&Cw20HookMsg::UnlockLP { locker_id: 0 }).unwrap(),
+        })
+    };
+
+    let err = execute(deps.as_mut(), env_at(DAY), mock_info("receipt", &[]), redeem(60))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidReceiptAmount {
+            expected: Uint128::new(100),
+        }
+    );
+
+    let err = execute(deps.as_mut(), env_at(DAY), mock_info(LP_B, &[]), redeem(100))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidReceipt {});
+
+    let res = execute(deps.as_mut(), env_at(DAY), mock_info("receipt", &[]), redeem(100))
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "receipt".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), USER.to_string(), Uint128::new(100))]
+    );
+}
+
+fn voting_power(deps: &MockDeps, address: Option<&str>, at_time: u64) -> Uint128 {
+    let msg = match address {
+        Some(address) => QueryMsg::VotingPower {
+            address: address.to_string(),
+            at_time: Some(at_time),
+        },
+        None => QueryMsg::TotalVotingPower {
+            at_time: Some(at_time),
+        },
+    };
+    let res: VotingPowerResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    res.power
+}
+
+#[test]
+fn voting_power_decays_linearly_and_keeps_history() {
+    let mut deps = setup();
+    let start = mock_env().block.time.seconds();
+    let locker_id = lock(&mut deps, USER, LP_A, 365_000, 365 * DAY);
+    lock(&mut deps, "other", LP_A, 1_000, 73 * DAY);
+
+    assert_eq!(voting_power(&deps, Some(USER), start), Uint128::new(365_000));
+    assert_eq!(voting_power(&deps, Some("other"), start), Uint128::new(200));
+    assert_eq!(voting_power(&deps, None, start), Uint128::new(365_200));
+
+    // Halfway through the user's lock, after the other lock expired
+    let half = start + 365 * DAY / 2;
+    assert_eq!(voting_power(&deps, Some(USER), half), Uint128::new(182_500));
+    assert_eq!(voting_power(&deps, None, half), Uint128::new(182_500));
+
+    execute(
+        deps.as_mut(),
+        env_at(365 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap();
+
+    assert_eq!(voting_power(&deps, None, start + 365 * DAY), Uint128::zero());
+    assert_eq!(voting_power(&deps, Some(USER), half), Uint128::new(182_500));
+}
+
+#[test]
+fn voting_power_follows_extension_and_auto_relock() {
+    let mut deps = setup();
+    let start = mock_env().block.time.seconds();
+    let locker_id = lock(&mut deps, USER, LP_A, 365_000, 73 * DAY);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::ExtendLock {
+            locker_id,
+            new_unlock_time: start + 146 * DAY,
+        },
+    )
+    .unwrap();
+    assert_eq!(voting_power(&deps, Some(USER), start), Uint128::new(146_000));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::EnableAutoRelock {
+            locker_id,
+            duration: 365 * DAY,
+        },
+    )
+    .unwrap();
+
+    // Rolling lockers keep their full power
+    assert_eq!(voting_power(&deps, Some(USER), start + 300 * DAY), Uint128::new(365_000));
+    assert_eq!(voting_power(&deps, None, start + 300 * DAY), Uint128::new(365_000));
+}
+
+#[test]
+fn locked_balances_are_snapshotted_per_height() {
+    let mut deps = setup();
+    let lock_height = mock_env().block.height;
+    let locker_id = lock(&mut deps, USER, LP_A, 100, DAY);
+    lock(&mut deps, "other", LP_A, 40, DAY);
+
+    let mut unlock_env = env_at(DAY);
+    unlock_env.block.height = lock_height + 10;
+    execute(
+        deps.as_mut(),
+        unlock_env,
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id },
+    )
+    .unwrap();
+
+    let balance_at = |height: u64| -> Uint128 {
+        let msg = QueryMsg::LockedBalanceAt {
+            owner: USER.to_string(),
+            lp_token: LP_A.to_string(),
+            height,
+        };
+        let res: LockedBalanceResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        res.amount
+    };
+    let total_at = |height: u64| -> Uint128 {
+        let msg = QueryMsg::TotalLockedAt {
+            lp_token: LP_A.to_string(),
+            height,
+        };
+        let res: TotalLockedResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        res.total_amount
+    };
+
+    // Snapshots reflect the state at the start of each block
+    assert_eq!(balance_at(lock_height), Uint128::zero());
+    assert_eq!(balance_at(lock_height + 1), Uint128::new(100));
+    assert_eq!(balance_at(lock_height + 10), Uint128::new(100));
+    assert_eq!(balance_at(lock_height + 11), Uint128::zero());
+    assert_eq!(total_at(lock_height + 5), Uint128::new(140));
+    assert_eq!(total_at(lock_height + 11), Uint128::new(40));
+}
+
+#[test]
+fn sudo_replaces_admin_pauses_and_force_releases() {
+    let mut deps = setup();
+    let locked = lock(&mut deps, USER, LP_A, 100, 300 * DAY);
+
+    let new_admin = SudoMsg::UpdateAdmin {
+        admin: "council".to_string(),
+    };
+    sudo(deps.as_mut(), mock_env(), new_admin).unwrap();
+    sudo(deps.as_mut(), mock_env(), SudoMsg::ForcePause {}).unwrap();
+
+    let config: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.admin, Addr::unchecked("council"));
+    assert!(config.paused);
+
+    // The previous admin key no longer has any power
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), ExecuteMsg::Resume {})
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::ForceRelease { locker_ids: vec![locked] },
+    )
+    .unwrap();
+    assert_eq!(
+        transfers(&res.messages),
+        vec![(LP_A.to_string(), USER.to_string(), Uint128::new(100))]
+    );
+
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::Locker { locker_id: locked }).is_err());
+
+    let err = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::ForceRelease { locker_ids: vec![locked] },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchFailed {
+            locker_id: locked,
+            reason: ContractError::LockerNotFound {}.to_string(),
+        }
+    );
+}
+
+#[test]
+fn config_updates_stay_within_bounds() {
+    let mut deps = setup();
+
+    let update = |delay: Option<u64>, fee: Option<u16>| ExecuteMsg::UpdateConfig {
+        admin: None,
+        reward_controller: None,
+        emergency_unlock_delay: delay,
+        platform_fee_bps: fee,
+        keeper_tip_bps: None,
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(Some(0), None))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidEmergencyUnlockDelay {
+            min: DAY,
+            max: 30 * DAY,
+        }
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(None, Some(10_001)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::PlatformFeeTooHigh { max: 1_000 });
+
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(Some(DAY), Some(500)))
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateLP {
+            lp_token: LP_A.to_string(),
+            min_lock_duration: None,
+            max_lock_duration: None,
+            bonus_multiplier: Some(Decimal::percent(50)),
+            tiers: None,
+            enabled: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidBonusMultiplier {
+            max: Decimal::percent(1_000),
+        }
+    );
+
+    // Governance can tighten the bounds, but not exclude the live config
+    let bounds = ConfigBounds {
+        min_emergency_unlock_delay: DAY,
+        max_emergency_unlock_delay: 7 * DAY,
+        max_platform_fee_bps: 100,
+        max_bonus_multiplier: Decimal::percent(300),
+    };
+    let err = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::UpdateBounds {
+            bounds: bounds.clone(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidBounds {});
+
+    let loosened = ConfigBounds {
+        max_platform_fee_bps: 5_000,
+        ..bounds.clone()
+    };
+    let err = sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateBounds { bounds: loosened })
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidBounds {});
+
+    let tightened = ConfigBounds {
+        max_platform_fee_bps: 500,
+        ..bounds
+    };
+    sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateBounds { bounds: tightened }).unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(Some(8 * DAY), None))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidEmergencyUnlockDelay {
+            min: DAY,
+            max: 7 * DAY,
+        }
+    );
+}
+
+/// Storage layout written by lp-locker 2.0.0
+mod v2_0_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Decimal, Uint128};
+    use cw_storage_plus::{Item, Map};
+
+    #[cw_serde]
+    pub struct Config {
+        pub admin: Addr,
+        pub reward_controller: Option<Addr>,
+        pub emergency_unlock_delay: u64,
+        pub platform_fee_bps: u16,
+        pub paused: bool,
+        pub next_locker_id: u64,
+    }
+
+    #[cw_serde]
+    pub struct Locker {
+        pub id: u64,
+        pub owner: Addr,
+        pub lp_token: Addr,
+        pub amount: Uint128,
+        pub locked_at: u64,
+        pub unlock_time: u64,
+        pub extended_count: u8,
+        pub emergency_unlock_requested: Option<u64>,
+        pub metadata: Option<String>,
+    }
+
+    #[cw_serde]
+    pub struct WhitelistedLP {
+        pub lp_token: Addr,
+        pub min_lock_duration: u64,
+        pub max_lock_duration: u64,
+        pub enabled: bool,
+        pub bonus_multiplier: Decimal,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+    pub const LOCKERS: Map<u64, Locker> = Map::new("lockers");
+    pub const USER_LOCKERS: Map<(&Addr, u64), bool> = Map::new("user_lockers");
+    pub const WHITELISTED_LPS: Map<&Addr, WhitelistedLP> = Map::new("whitelisted_lps");
+    pub const TOTAL_LOCKED: Map<&Addr, Uint128> = Map::new("total_locked");
+}
+
+fn v2_0_0_state() -> MockDeps {
+    let mut deps = mock_dependencies();
+    let storage = deps.as_mut().storage;
+    let now = mock_env().block.time.seconds();
+    let (owner, lp_token) = (Addr::unchecked(USER), Addr::unchecked(LP_A));
+
+    cw2::set_contract_version(storage, "crates.io:lp-locker", "2.0.0").unwrap();
+    v2_0_0::CONFIG
+        .save(
+            storage,
+            &v2_0_0::Config {
+                admin: Addr::unchecked(ADMIN),
+                reward_controller: None,
+                emergency_unlock_delay: 3 * DAY,
+                platform_fee_bps: 0,
+                paused: false,
+                next_locker_id: 1,
+            },
+        )
+        .unwrap();
+    v2_0_0::WHITELISTED_LPS
+        .save(
+            storage,
+            &lp_token,
+            &v2_0_0::WhitelistedLP {
+                lp_token: lp_token.clone(),
+                min_lock_duration: DAY,
+                max_lock_duration: 365 * DAY,
+                enabled: true,
+                bonus_multiplier: Decimal::one(),
+            },
+        )
+        .unwrap();
+    v2_0_0::LOCKERS
+        .save(
+            storage,
+            0,
+            &v2_0_0::Locker {
+                id: 0,
+                owner: owner.clone(),
+                lp_token: lp_token.clone(),
+                amount: Uint128::new(365_000),
+                locked_at: now,
+                unlock_time: now + 365 * DAY,
+                extended_count: 0,
+                emergency_unlock_requested: None,
+                metadata: Some("Team tokens".to_string()),
+            },
+        )
+        .unwrap();
+    v2_0_0::USER_LOCKERS.save(storage, (&owner, 0), &true).unwrap();
+    v2_0_0::TOTAL_LOCKED
+        .save(storage, &lp_token, &Uint128::new(365_000))
+        .unwrap();
+
+    deps
+}
+
+#[test]
+fn migrate_from_v2_0_0_backfills_indexes() {
+    let mut deps = v2_0_0_state();
+    let env = mock_env();
+
+    migrate(deps.as_mut(), env.clone(), MigrateMsg { reward_controller: None }).unwrap();
+
+    // Old records load with defaults for new fields
+    let locker = query_locker(&deps, 0);
+    assert_eq!(locker.auto_relock, None);
+    assert_eq!(locker.bonus_multiplier, Decimal::one());
+    assert_eq!(
+        locker.metadata.unwrap().description,
+        Some("Team tokens".to_string())
+    );
+    assert_eq!(voting_power(&deps, Some(USER), env.block.time.seconds()), Uint128::new(365_000));
+
+    let res: LockedBalanceResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LockedBalanceAt {
+                owner: USER.to_string(),
+                lp_token: LP_A.to_string(),
+                height: env.block.height + 1,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.amount, Uint128::new(365_000));
+
+    let msg = QueryMsg::OwnerPortfolio {
+        owner: USER.to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let portfolio: OwnerPortfolioResponse =
+        from_json(query(deps.as_ref(), env_at(365 * DAY), msg).unwrap()).unwrap();
+    assert_eq!(portfolio.entries[0].unlockable, Uint128::new(365_000));
+
+    let history: Vec<MigrationRecord> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MigrationHistory {}).unwrap())
+            .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].from_version, "2.0.0");
+    assert_eq!(
+        history[0].steps,
+        vec![
+            "structure_locker_metadata".to_string(),
+            "backfill_locker_indexes".to_string(),
+        ]
+    );
+
+    // A second migration in the same block gets its own record
+    migrate(deps.as_mut(), env.clone(), MigrateMsg { reward_controller: None }).unwrap();
+    let history: Vec<MigrationRecord> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MigrationHistory {}).unwrap())
+            .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].from_version, env!("CARGO_PKG_VERSION"));
+    assert!(history[1].steps.is_empty());
+
+    // The unlocked locker keeps working with the migrated state
+    execute(
+        deps.as_mut(),
+        env_at(365 * DAY),
+        mock_info(USER, &[]),
+        ExecuteMsg::UnlockLP { locker_id: 0 },
+    )
+    .unwrap();
+}
+
+#[test]
+fn migrate_rejects_downgrade_and_foreign_contract() {
+    let mut deps = v2_0_0_state();
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:lp-locker", "9.0.0").unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { reward_controller: None })
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MigrationDowngrade {
+            from: "9.0.0".to_string(),
+            to: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
+
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:other", "1.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { reward_controller: None })
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMigration {});
 }
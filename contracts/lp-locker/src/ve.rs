@@ -0,0 +1,184 @@
+//! Vote-escrow voting power derived from lockers.
+//!
+//! A locker's power is `amount * remaining / max_lock_duration` and decays
+//! linearly to zero at its unlock time. Rolling (auto-relock) lockers keep a
+//! constant power for their rolling duration. Totals are tracked with slope
+//! checkpoints so queries never iterate over lockers.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal256, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Map};
+
+use crate::state::Locker;
+
+/// Aggregated voting power at `ts`; `bias` decays by `slope` every second
+#[cw_serde]
+#[derive(Default)]
+pub struct Point {
+    pub bias: Decimal256,
+    pub slope: Decimal256,
+    pub ts: u64,
+}
+
+/// Contribution of a single locker to the aggregated points
+#[cw_serde]
+pub struct VeLock {
+    pub slope: Decimal256,
+    pub end: u64,
+    /// Constant power of a rolling locker, which does not decay
+    pub rolling_bias: Option<Decimal256>,
+}
+
+pub const VE_LOCKS: Map<u64, VeLock> = Map::new("ve_locks");
+pub const TOTAL_POINTS: Map<u64, Point> = Map::new("ve_total_points");
+pub const TOTAL_SLOPE_CHANGES: Map<u64, Decimal256> = Map::new("ve_total_slope_changes");
+pub const USER_POINTS: Map<(&Addr, u64), Point> = Map::new("ve_user_points");
+pub const USER_SLOPE_CHANGES: Map<(&Addr, u64), Decimal256> =
+    Map::new("ve_user_slope_changes");
+
+impl VeLock {
+    pub fn new(locker: &Locker, max_lock_duration: u64) -> Self {
+        let slope = Decimal256::from_ratio(locker.amount, max_lock_duration.max(1));
+        VeLock {
+            slope,
+            end: locker.unlock_time,
+            rolling_bias: locker.auto_relock.map(|duration| decay(slope, duration)),
+        }
+    }
+
+    /// Power of this lock at `time`
+    pub fn power_at(&self, time: u64) -> Decimal256 {
+        match self.rolling_bias {
+            Some(bias) => bias,
+            None => decay(self.slope, self.end.saturating_sub(time)),
+        }
+    }
+
+    /// Slope that still has to be applied after `time`
+    fn active_slope(&self, time: u64) -> Decimal256 {
+        if self.rolling_bias.is_none() && self.end > time {
+            self.slope
+        } else {
+            Decimal256::zero()
+        }
+    }
+}
+
+/// Records the new voting power of a locker (`None` once it is released)
+pub fn checkpoint_locker(
+    storage: &mut dyn Storage,
+    now: u64,
+    locker: &Locker,
+    max_lock_duration: Option<u64>,
+) -> StdResult<()> {
+    let old = VE_LOCKS.may_load(storage, locker.id)?;
+    let new = max_lock_duration.map(|max| VeLock::new(locker, max));
+
+    for owner in [None, Some(&locker.owner)] {
+        let mut point = point_at(storage, owner, now)?;
+
+        if let Some(old) = &old {
+            point.bias = point.bias.saturating_sub(old.power_at(now));
+            let slope = old.active_slope(now);
+            if !slope.is_zero() {
+                point.slope = point.slope.saturating_sub(slope);
+                update_slope_change(storage, owner, old.end, slope, false)?;
+            }
+        }
+
+        if let Some(new) = &new {
+            point.bias += new.power_at(now);
+            let slope = new.active_slope(now);
+            if !slope.is_zero() {
+                point.slope += slope;
+                update_slope_change(storage, owner, new.end, slope, true)?;
+            }
+        }
+
+        save_point(storage, owner, &point)?;
+    }
+
+    match new {
+        Some(new) => VE_LOCKS.save(storage, locker.id, &new),
+        None => {
+            VE_LOCKS.remove(storage, locker.id);
+            Ok(())
+        }
+    }
+}
+
+/// Voting power of `owner` (or of everyone when `None`) at `time`,
+/// rounded to the nearest unit to absorb slope truncation
+pub fn voting_power(storage: &dyn Storage, owner: Option<&Addr>, time: u64) -> StdResult<Uint128> {
+    let bias = point_at(storage, owner, time)?.bias;
+    let power = (bias + Decimal256::percent(50)).to_uint_floor();
+    Ok(Uint128::try_from(power)?)
+}
+
+/// Latest checkpoint at or before `time`, advanced to `time`
+fn point_at(storage: &dyn Storage, owner: Option<&Addr>, time: u64) -> StdResult<Point> {
+    let end = Some(Bound::inclusive(time));
+    let last = match owner {
+        Some(owner) => USER_POINTS
+            .prefix(owner)
+            .range(storage, None, end, Order::Descending)
+            .next(),
+        None => TOTAL_POINTS.range(storage, None, end, Order::Descending).next(),
+    };
+
+    let mut point = match last.transpose()? {
+        Some((_, point)) => point,
+        None => return Ok(Point { ts: time, ..Point::default() }),
+    };
+
+    let start = Some(Bound::exclusive(point.ts));
+    let end = Some(Bound::inclusive(time));
+    let changes: Vec<(u64, Decimal256)> = match owner {
+        Some(owner) => USER_SLOPE_CHANGES
+            .prefix(owner)
+            .range(storage, start, end, Order::Ascending)
+            .collect::<StdResult<_>>()?,
+        None => TOTAL_SLOPE_CHANGES
+            .range(storage, start, end, Order::Ascending)
+            .collect::<StdResult<_>>()?,
+    };
+
+    for (ts, slope_change) in changes {
+        point.bias = point.bias.saturating_sub(decay(point.slope, ts - point.ts));
+        point.slope = point.slope.saturating_sub(slope_change);
+        point.ts = ts;
+    }
+    point.bias = point.bias.saturating_sub(decay(point.slope, time - point.ts));
+    point.ts = time;
+
+    Ok(point)
+}
+
+fn save_point(storage: &mut dyn Storage, owner: Option<&Addr>, point: &Point) -> StdResult<()> {
+    match owner {
+        Some(owner) => USER_POINTS.save(storage, (owner, point.ts), point),
+        None => TOTAL_POINTS.save(storage, point.ts, point),
+    }
+}
+
+fn update_slope_change(
+    storage: &mut dyn Storage,
+    owner: Option<&Addr>,
+    ts: u64,
+    slope: Decimal256,
+    add: bool,
+) -> StdResult<()> {
+    let apply = |change: Option<Decimal256>| -> StdResult<_> {
+        let change = change.unwrap_or_default();
+        Ok(if add { change + slope } else { change.saturating_sub(slope) })
+    };
+    match owner {
+        Some(owner) => USER_SLOPE_CHANGES.update(storage, (owner, ts), apply)?,
+        None => TOTAL_SLOPE_CHANGES.update(storage, ts, apply)?,
+    };
+    Ok(())
+}
+
+fn decay(slope: Decimal256, seconds: u64) -> Decimal256 {
+    slope * Decimal256::from_ratio(seconds, 1u64)
+}
@@ -1,19 +1,27 @@
 use cosmwasm_std::{
     entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128, Decimal, Addr, CosmosMsg, WasmMsg, BankMsg, Coin,
+    Response, StdResult, Uint128, Decimal, Addr, CosmosMsg, WasmMsg, BankMsg, Coin, Event,
+    Storage,
 };
 use cw2::set_contract_version;
 use cw20::Cw20ExecuteMsg;
+use lp_events::rewards as events;
 
 use crate::error::ContractError;
 use crate::msg::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
     RewardPoolResponse, UserStakeResponse, PendingRewardsResponse,
+    LockerInfo, LockerQueryMsg, OperatorInfo, WhitelistedLPInfo, MigrateMsg, SudoMsg,
+    ScreeningQueryMsg, ScreeningResponse,
 };
+use crate::migrations;
 use crate::state::{
-    RewardConfig, RewardPool, UserStake, UserReward, AssetInfo,
-    CONFIG, POOLS, USER_STAKES, USER_REWARDS, TOTAL_STAKED,
+    ConfigBounds, RewardConfig, RewardPool, UserStake, UserReward, AssetInfo,
+    CONFIG, POOLS, USER_STAKES, USER_REWARDS, TOTAL_STAKED, MIGRATION_HISTORY, MigrationRecord,
+    BLOCKLIST, LOCKER_STAKERS,
+    MAX_CLAIM_INTERVAL, MIN_CLAIM_INTERVAL,
 };
+use semver::Version;
 
 const CONTRACT_NAME: &str = "crates.io:reward-controller";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -36,13 +44,16 @@ pub fn instantiate(
         paused: false,
         claim_interval: msg.claim_interval.unwrap_or(3600), // 1 hour default
         next_pool_id: 0,
+        bounds: ConfigBounds::default(),
+        screening_contract: None,
     };
 
+    validate_config(&config)?;
     CONFIG.save(deps.storage, &config)?;
     TOTAL_STAKED.save(deps.storage, &Uint128::zero())?;
 
     Ok(Response::new()
-        .add_attribute("method", "instantiate")
+        .add_attribute("action", "instantiate")
         .add_attribute("admin", msg.admin)
         .add_attribute("lp_locker_contract", msg.lp_locker_contract))
 }
@@ -61,6 +72,15 @@ pub fn execute(
         ExecuteMsg::UnregisterStake { locker_id } => {
             execute_unregister_stake(deps, env, info, locker_id)
         }
+        ExecuteMsg::LockerTransferred { locker_id } => {
+            execute_locker_transferred(deps, info, locker_id)
+        }
+        ExecuteMsg::LockerUpdated { locker_id } => {
+            execute_locker_updated(deps, env, info, locker_id)
+        }
+        ExecuteMsg::LockersClosed { locker_ids } => {
+            execute_lockers_closed(deps, info, locker_ids)
+        }
         ExecuteMsg::ClaimRewards { pool_ids } => {
             execute_claim_rewards(deps, env, info, pool_ids)
         }
@@ -89,6 +109,10 @@ pub fn execute(
         } => execute_update_config(deps, info, admin, lp_locker_contract, claim_interval),
         ExecuteMsg::Pause {} => execute_pause(deps, info),
         ExecuteMsg::Resume {} => execute_resume(deps, info),
+        ExecuteMsg::UpdateBlocklist { add, remove } => {
+            execute_update_blocklist(deps, info, add, remove)
+        }
+        ExecuteMsg::SetScreening { contract } => execute_set_screening(deps, info, contract),
     }
 }
 
@@ -105,29 +129,92 @@ fn execute_register_stake(
     }
 
     // Query LP locker to verify locker exists and get details
-    // TODO: Implement actual query to lp-locker contract
-    // For now, we'll create a simple stake record
+    let locker: LockerInfo = deps
+        .querier
+        .query_wasm_smart(
+            &config.lp_locker_contract,
+            &LockerQueryMsg::Locker { locker_id },
+        )
+        .map_err(|_| ContractError::InvalidLocker {})?;
+
+    // Operators approved for `register_rewards` register on the owner's behalf
+    if locker.owner != info.sender {
+        let approval: OperatorInfo = deps.querier.query_wasm_smart(
+            &config.lp_locker_contract,
+            &LockerQueryMsg::Operator {
+                owner: locker.owner.to_string(),
+                operator: info.sender.to_string(),
+            },
+        )?;
+
+        if approval.expires.is_expired(&env.block)
+            || !approval.permissions.iter().any(|p| p == "register_rewards")
+        {
+            return Err(ContractError::InvalidLocker {});
+        }
+    }
+    let user = locker.owner.clone();
+    ensure_not_screened(deps.as_ref(), &config, &user)?;
+
+    let lock_duration = stake_duration(&locker, env.block.time.seconds())
+        .ok_or(ContractError::InvalidLocker {})?;
+
+    // Older lockers have no tier and use the LP's flat multiplier
+    let bonus_multiplier = match locker.bonus_multiplier {
+        Some(multiplier) => multiplier,
+        None => {
+            let whitelist: WhitelistedLPInfo = deps.querier.query_wasm_smart(
+                &config.lp_locker_contract,
+                &LockerQueryMsg::WhitelistedLP {
+                    lp_token: locker.lp_token.to_string(),
+                },
+            )?;
+            whitelist.bonus_multiplier
+        }
+    };
 
     let stake = UserStake {
-        user: info.sender.clone(),
+        user: user.clone(),
         locker_id,
-        lp_amount: Uint128::from(1000000u128), // TODO: Get from locker query
-        lock_start: env.block.time.seconds(),
-        lock_duration: 86400 * 30, // TODO: Calculate from locker
-        bonus_multiplier: Decimal::one(), // TODO: Get from whitelist
+        lp_amount: locker.amount,
+        lock_start: locker.locked_at,
+        lock_duration,
+        bonus_multiplier,
     };
 
-    USER_STAKES.save(deps.storage, (&info.sender, locker_id), &stake)?;
+    // A stake left behind by a previous owner of the locker moves to the current one
+    if let Some(staker) = LOCKER_STAKERS.may_load(deps.storage, locker_id)? {
+        if staker != user {
+            remove_stake(deps.storage, &staker, locker_id)?;
+        }
+    }
+
+    // Re-registering refreshes the stake instead of counting it twice
+    let previous = USER_STAKES
+        .may_load(deps.storage, (&user, locker_id))?
+        .map(|stake| stake.lp_amount)
+        .unwrap_or_default();
+
+    USER_STAKES.save(deps.storage, (&user, locker_id), &stake)?;
+    LOCKER_STAKERS.save(deps.storage, locker_id, &user)?;
 
     // Update total staked
     TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
-        Ok(total.checked_add(stake.lp_amount)?)
+        Ok(total.checked_sub(previous)?.checked_add(stake.lp_amount)?)
     })?;
 
     Ok(Response::new()
+        .add_event(Event::from(events::StakeRegistered {
+            user: user.clone(),
+            locker_id,
+            lp_amount: stake.lp_amount,
+            lock_duration,
+            bonus_multiplier: stake.bonus_multiplier,
+        }))
         .add_attribute("action", "register_stake")
-        .add_attribute("user", info.sender)
-        .add_attribute("locker_id", locker_id.to_string()))
+        .add_attribute("user", user)
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("lock_duration", lock_duration.to_string()))
 }
 
 fn execute_unregister_stake(
@@ -136,18 +223,144 @@ fn execute_unregister_stake(
     info: MessageInfo,
     locker_id: u64,
 ) -> Result<Response, ContractError> {
-    let stake = USER_STAKES.load(deps.storage, (&info.sender, locker_id))?;
+    let stake = remove_stake(deps.storage, &info.sender, locker_id)?;
 
-    USER_STAKES.remove(deps.storage, (&info.sender, locker_id));
+    Ok(Response::new()
+        .add_event(Event::from(events::StakeUnregistered {
+            user: info.sender,
+            locker_id,
+            lp_amount: stake.lp_amount,
+        }))
+        .add_attribute("action", "unregister_stake")
+        .add_attribute("locker_id", locker_id.to_string()))
+}
+
+fn execute_locker_transferred(
+    deps: DepsMut,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.lp_locker_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(Response::new()
+        .add_events(drop_locker_stake(deps.storage, locker_id)?)
+        .add_attribute("action", "locker_transferred")
+        .add_attribute("locker_id", locker_id.to_string()))
+}
+
+fn execute_locker_updated(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    locker_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.lp_locker_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "locker_updated")
+        .add_attribute("locker_id", locker_id.to_string());
+    let Some(user) = LOCKER_STAKERS.may_load(deps.storage, locker_id)? else {
+        return Ok(response);
+    };
+
+    let locker: LockerInfo = deps.querier.query_wasm_smart(
+        &config.lp_locker_contract,
+        &LockerQueryMsg::Locker { locker_id },
+    )?;
+
+    // A locker that no longer earns rewards loses its stake
+    let lock_duration = stake_duration(&locker, env.block.time.seconds())
+        .filter(|_| locker.owner == user);
+    let Some(lock_duration) = lock_duration else {
+        return Ok(response.add_events(drop_locker_stake(deps.storage, locker_id)?));
+    };
+
+    let mut stake = USER_STAKES.load(deps.storage, (&user, locker_id))?;
+    let previous = stake.lp_amount;
+    stake.lp_amount = locker.amount;
+    stake.lock_duration = lock_duration;
+    USER_STAKES.save(deps.storage, (&user, locker_id), &stake)?;
 
-    // Update total staked
     TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
-        Ok(total.checked_sub(stake.lp_amount)?)
+        Ok(total.checked_sub(previous)?.checked_add(stake.lp_amount)?)
     })?;
 
+    Ok(response.add_attribute("lock_duration", lock_duration.to_string()))
+}
+
+fn execute_lockers_closed(
+    deps: DepsMut,
+    info: MessageInfo,
+    locker_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.lp_locker_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut unstaked = vec![];
+    for locker_id in &locker_ids {
+        unstaked.extend(drop_locker_stake(deps.storage, *locker_id)?);
+    }
+
     Ok(Response::new()
-        .add_attribute("action", "unregister_stake")
-        .add_attribute("locker_id", locker_id.to_string()))
+        .add_events(unstaked)
+        .add_attribute("action", "lockers_closed")
+        .add_attribute(
+            "locker_ids",
+            locker_ids.iter().map(u64::to_string).collect::<Vec<_>>().join(","),
+        ))
+}
+
+/// Duration a locker counts for, or `None` once it no longer earns rewards.
+/// Rolling lockers always count for their full rolling duration.
+fn stake_duration(locker: &LockerInfo, current_time: u64) -> Option<u64> {
+    match locker.auto_relock {
+        Some(duration) => Some(duration),
+        None if locker.unlock_time > current_time => Some(locker.unlock_time - locker.locked_at),
+        None => None,
+    }
+}
+
+/// Removes whatever stake a locker has, returning the matching event
+fn drop_locker_stake(storage: &mut dyn Storage, locker_id: u64) -> StdResult<Option<Event>> {
+    let Some(user) = LOCKER_STAKERS.may_load(storage, locker_id)? else {
+        return Ok(None);
+    };
+    let stake = remove_stake(storage, &user, locker_id)?;
+
+    Ok(Some(Event::from(events::StakeUnregistered {
+        user,
+        locker_id,
+        lp_amount: stake.lp_amount,
+    })))
+}
+
+/// Removes a stake and takes its amount off the total
+fn remove_stake(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    locker_id: u64,
+) -> StdResult<UserStake> {
+    let stake = USER_STAKES.load(storage, (user, locker_id))?;
+
+    USER_STAKES.remove(storage, (user, locker_id));
+    LOCKER_STAKERS.remove(storage, locker_id);
+
+    TOTAL_STAKED.update(storage, |total| -> StdResult<_> {
+        Ok(total.checked_sub(stake.lp_amount)?)
+    })?;
+
+    Ok(stake)
 }
 
 fn execute_claim_rewards(
@@ -157,7 +370,10 @@ fn execute_claim_rewards(
     pool_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    ensure_not_screened(deps.as_ref(), &config, &info.sender)?;
+
     let mut messages: Vec<CosmosMsg> = vec![];
+    let mut claimed: Vec<Event> = vec![];
     let mut total_claimed = Uint128::zero();
 
     for pool_id in pool_ids {
@@ -214,6 +430,11 @@ fn execute_claim_rewards(
         };
 
         messages.push(transfer_msg);
+        claimed.push(Event::from(events::RewardsClaimed {
+            user: info.sender.clone(),
+            pool_id,
+            amount: pending,
+        }));
         total_claimed = total_claimed.checked_add(pending)?;
     }
 
@@ -223,6 +444,7 @@ fn execute_claim_rewards(
 
     Ok(Response::new()
         .add_messages(messages)
+        .add_events(claimed)
         .add_attribute("action", "claim_rewards")
         .add_attribute("total_claimed", total_claimed))
 }
@@ -261,7 +483,19 @@ fn execute_create_pool(
 
     POOLS.save(deps.storage, pool_id, &pool)?;
 
+    let reward_token = match pool.reward_token {
+        AssetInfo::Cw20(addr) => addr.into_string(),
+        AssetInfo::Native(denom) => denom,
+    };
+
     Ok(Response::new()
+        .add_event(Event::from(events::PoolCreated {
+            pool_id,
+            reward_token,
+            emission_per_second,
+            start_time,
+            end_time,
+        }))
         .add_attribute("action", "create_reward_pool")
         .add_attribute("pool_id", pool_id.to_string()))
 }
@@ -322,6 +556,12 @@ fn execute_deposit_rewards(
     POOLS.save(deps.storage, pool_id, &pool)?;
 
     Ok(Response::new()
+        .add_event(Event::from(events::PoolFunded {
+            pool_id,
+            deposited: deposit_amount,
+            withdrawn: Uint128::zero(),
+            total_deposited: pool.total_deposited,
+        }))
         .add_attribute("action", "deposit_rewards")
         .add_attribute("amount", deposit_amount))
 }
@@ -350,10 +590,58 @@ fn execute_withdraw_rewards(
     POOLS.save(deps.storage, pool_id, &pool)?;
 
     Ok(Response::new()
+        .add_event(Event::from(events::PoolFunded {
+            pool_id,
+            deposited: Uint128::zero(),
+            withdrawn: amount,
+            total_deposited: pool.total_deposited,
+        }))
         .add_attribute("action", "withdraw_rewards")
         .add_attribute("amount", amount))
 }
 
+fn execute_update_blocklist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in &add {
+        BLOCKLIST.save(deps.storage, &deps.api.addr_validate(address)?, &true)?;
+    }
+    for address in &remove {
+        BLOCKLIST.remove(deps.storage, &deps.api.addr_validate(address)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_blocklist")
+        .add_attribute("added", add.join(","))
+        .add_attribute("removed", remove.join(",")))
+}
+
+fn execute_set_screening(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.screening_contract = contract.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "set_screening"))
+}
+
 fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -379,6 +667,7 @@ fn execute_update_config(
         config.claim_interval = interval;
     }
 
+    validate_config(&config)?;
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attribute("action", "update_config"))
@@ -410,10 +699,57 @@ fn execute_resume(deps: DepsMut, info: MessageInfo) -> Result<Response, Contract
     Ok(Response::new().add_attribute("action", "resume"))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::ForcePause {} => sudo_force_pause(deps),
+        SudoMsg::UpdateAdmin { admin } => sudo_update_admin(deps, admin),
+        SudoMsg::UpdateBounds { bounds } => sudo_update_bounds(deps, bounds),
+    }
+}
+
+fn sudo_force_pause(deps: DepsMut) -> Result<Response, ContractError> {
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.paused = true;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "sudo_force_pause"))
+}
+
+fn sudo_update_admin(deps: DepsMut, admin: String) -> Result<Response, ContractError> {
+    let admin = deps.api.addr_validate(&admin)?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.admin = admin.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_update_admin")
+        .add_attribute("admin", admin))
+}
+
+fn sudo_update_bounds(deps: DepsMut, bounds: ConfigBounds) -> Result<Response, ContractError> {
+    if bounds.min_claim_interval < MIN_CLAIM_INTERVAL
+        || bounds.min_claim_interval > bounds.max_claim_interval
+        || bounds.max_claim_interval > MAX_CLAIM_INTERVAL
+    {
+        return Err(ContractError::InvalidBounds {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.bounds = bounds;
+    validate_config(&config).map_err(|_| ContractError::InvalidBounds {})?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "sudo_update_bounds"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::IsBlocked { address } => to_json_binary(&query_is_blocked(deps, address)?),
         QueryMsg::RewardPool { pool_id } => to_json_binary(&query_pool(deps, pool_id)?),
         QueryMsg::AllRewardPools { start_after, limit } => {
             to_json_binary(&query_all_pools(deps, start_after, limit)?)
@@ -424,6 +760,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::PendingRewards { user, pool_id } => {
             to_json_binary(&query_pending_rewards(deps, user, pool_id)?)
         }
+        QueryMsg::MigrationHistory {} => to_json_binary(&query_migration_history(deps)?),
     }
 }
 
@@ -435,6 +772,8 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         paused: config.paused,
         claim_interval: config.claim_interval,
         next_pool_id: config.next_pool_id,
+        bounds: config.bounds,
+        screening_contract: config.screening_contract,
     })
 }
 
@@ -458,7 +797,7 @@ fn query_all_pools(
     limit: Option<u32>,
 ) -> StdResult<Vec<RewardPoolResponse>> {
     let limit = limit.unwrap_or(10).min(30) as usize;
-    let start = start_after.map(|id| cw_storage_plus::Bound::exclusive(id));
+    let start = start_after.map(cw_storage_plus::Bound::exclusive);
 
     POOLS
         .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
@@ -503,6 +842,113 @@ fn query_pending_rewards(deps: Deps, user: String, pool_id: u64) -> StdResult<Pe
     })
 }
 
+fn query_is_blocked(deps: Deps, address: String) -> StdResult<ScreeningResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(ScreeningResponse {
+        blocked: BLOCKLIST.has(deps.storage, &address),
+    })
+}
+
+fn query_migration_history(deps: Deps) -> StdResult<Vec<MigrationRecord>> {
+    MIGRATION_HISTORY
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    mut deps: DepsMut,
+    env: Env,
+    _msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    let version = cw2::get_contract_version(deps.storage)?;
+
+    if version.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {});
+    }
+
+    let from = Version::parse(&version.version).map_err(|_| ContractError::InvalidMigration {})?;
+    let to = Version::parse(CONTRACT_VERSION).map_err(|_| ContractError::InvalidMigration {})?;
+
+    if from > to {
+        return Err(ContractError::MigrationDowngrade {
+            from: version.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    let steps = migrations::run(deps.branch(), &env, &from, &to)?;
+    validate_config(&CONFIG.load(deps.storage)?)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Several migrations can run in one block, so records are numbered instead
+    let sequence = MIGRATION_HISTORY
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    MIGRATION_HISTORY.save(
+        deps.storage,
+        sequence,
+        &MigrationRecord {
+            from_version: version.version.clone(),
+            to_version: CONTRACT_VERSION.to_string(),
+            steps: steps.clone(),
+            height: env.block.height,
+            time: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", version.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("steps", steps.join(",")))
+}
+
+/// Checks the blocklist, then the screening contract if one is set
+fn ensure_not_screened(
+    deps: Deps,
+    config: &RewardConfig,
+    address: &Addr,
+) -> Result<(), ContractError> {
+    let blocked = BLOCKLIST.has(deps.storage, address)
+        || match &config.screening_contract {
+            Some(contract) => {
+                let screening: ScreeningResponse = deps.querier.query_wasm_smart(
+                    contract,
+                    &ScreeningQueryMsg::IsBlocked {
+                        address: address.to_string(),
+                    },
+                )?;
+                screening.blocked
+            }
+            None => false,
+        };
+
+    if blocked {
+        return Err(ContractError::Blocked {
+            address: address.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_config(config: &RewardConfig) -> Result<(), ContractError> {
+    let bounds = &config.bounds;
+    if config.claim_interval < bounds.min_claim_interval
+        || config.claim_interval > bounds.max_claim_interval
+    {
+        return Err(ContractError::InvalidClaimInterval {
+            min: bounds.min_claim_interval,
+            max: bounds.max_claim_interval,
+        });
+    }
+    Ok(())
+}
+
 // Helper function
 fn calculate_pending_rewards(deps: Deps, user: &Addr, pool_id: u64) -> StdResult<Uint128> {
     // Simplified calculation - actual implementation would be more complex
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +6,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -35,4 +38,19 @@ pub enum ContractError {
 
     #[error("Insufficient reward balance")]
     InsufficientRewards {},
+
+    #[error("Claim interval must be between {min} and {max} seconds")]
+    InvalidClaimInterval { min: u64, max: u64 },
+
+    #[error("Bounds exceed the hard limits or exclude the current config")]
+    InvalidBounds {},
+
+    #[error("Address {address} is blocked")]
+    Blocked { address: String },
+
+    #[error("Invalid migration")]
+    InvalidMigration {},
+
+    #[error("Cannot migrate from version {from} to older version {to}")]
+    MigrationDowngrade { from: String, to: String },
 }
@@ -0,0 +1,48 @@
+//! Versioned state migrations.
+//!
+//! Each step upgrades the state written by the previous release. `migrate`
+//! runs, in order, every step whose version is newer than the stored contract
+//! version and not newer than the version being deployed.
+
+use cosmwasm_std::{DepsMut, Env, Order, StdError, StdResult};
+use semver::Version;
+
+use crate::state::{LOCKER_STAKERS, USER_STAKES};
+
+pub struct Migration {
+    pub version: &'static str,
+    pub name: &'static str,
+    pub run: fn(DepsMut, &Env) -> StdResult<()>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: "2.1.0",
+    name: "index_locker_stakers",
+    run: index_locker_stakers,
+}];
+
+/// Applies every step in `(from, to]` and returns the names of the applied steps
+pub fn run(mut deps: DepsMut, env: &Env, from: &Version, to: &Version) -> StdResult<Vec<String>> {
+    let mut applied = vec![];
+    for migration in MIGRATIONS {
+        let version = Version::parse(migration.version)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+        if version > *from && version <= *to {
+            (migration.run)(deps.branch(), env)?;
+            applied.push(migration.name.to_string());
+        }
+    }
+    Ok(applied)
+}
+
+/// 2.0.0 -> 2.1.0: record the user holding each locker's stake
+fn index_locker_stakers(deps: DepsMut, _env: &Env) -> StdResult<()> {
+    let stakes: Vec<_> = USER_STAKES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    for (user, locker_id) in stakes {
+        LOCKER_STAKERS.save(deps.storage, locker_id, &user)?;
+    }
+    Ok(())
+}
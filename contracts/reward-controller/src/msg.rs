@@ -1,6 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Decimal, Uint128};
-use crate::state::AssetInfo;
+use cw20::Expiration;
+use serde::{Deserialize, Serialize};
+use crate::state::{AssetInfo, ConfigBounds, MigrationRecord};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -17,6 +19,18 @@ pub enum ExecuteMsg {
     UnregisterStake {
         locker_id: u64,
     },
+    /// LP locker: drop the stake of a locker that changed owner
+    LockerTransferred {
+        locker_id: u64,
+    },
+    /// LP locker: re-read the lock terms of a staked locker
+    LockerUpdated {
+        locker_id: u64,
+    },
+    /// LP locker: drop the stakes of lockers that were unlocked
+    LockersClosed {
+        locker_ids: Vec<u64>,
+    },
     ClaimRewards {
         pool_ids: Vec<u64>,
     },
@@ -46,6 +60,15 @@ pub enum ExecuteMsg {
     },
     Pause {},
     Resume {},
+    /// Admin: Add addresses to, or remove them from, the blocklist
+    UpdateBlocklist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Admin: Set or clear the screening contract
+    SetScreening {
+        contract: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -74,6 +97,13 @@ pub enum QueryMsg {
         user: String,
         pool_id: u64,
     },
+
+    #[returns(Vec<MigrationRecord>)]
+    MigrationHistory {},
+
+    /// Whether `address` is on the blocklist
+    #[returns(ScreeningResponse)]
+    IsBlocked { address: String },
 }
 
 #[cw_serde]
@@ -83,6 +113,8 @@ pub struct ConfigResponse {
     pub paused: bool,
     pub claim_interval: u64,
     pub next_pool_id: u64,
+    pub bounds: ConfigBounds,
+    pub screening_contract: Option<Addr>,
 }
 
 #[cw_serde]
@@ -112,3 +144,67 @@ pub struct PendingRewardsResponse {
     pub pool_id: u64,
     pub pending_amount: Uint128,
 }
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Chain-governance powers, callable only through `sudo`
+#[cw_serde]
+pub enum SudoMsg {
+    /// Pause the contract, same as the admin `Pause`
+    ForcePause {},
+    
+    /// Replace the admin, e.g. after the admin key is lost or compromised
+    UpdateAdmin { admin: String },
+    
+    /// Change the limits enforced on admin-set parameters, within the hard limits
+    UpdateBounds { bounds: ConfigBounds },
+}
+
+// LP locker interface (subset of the lp-locker query API)
+#[cw_serde]
+pub enum LockerQueryMsg {
+    Locker { locker_id: u64 },
+    WhitelistedLP { lp_token: String },
+    Operator { owner: String, operator: String },
+}
+
+/// Fields of the lp-locker `LockerResponse` used for staking.
+/// Unknown fields are ignored so newer locker versions stay compatible.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LockerInfo {
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub locked_at: u64,
+    pub unlock_time: u64,
+    #[serde(default)]
+    pub auto_relock: Option<u64>,
+    /// Multiplier of the locker's tier; missing on lockers from before tiers
+    #[serde(default)]
+    pub bonus_multiplier: Option<Decimal>,
+}
+
+/// Operator approval granted by a locker owner on the lp-locker
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OperatorInfo {
+    pub permissions: Vec<String>,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WhitelistedLPInfo {
+    pub bonus_multiplier: Decimal,
+}
+
+/// Query interface expected from the screening contract; the lp-locker
+/// implements it for its own blocklist
+#[cw_serde]
+pub enum ScreeningQueryMsg {
+    IsBlocked { address: String },
+}
+
+#[cw_serde]
+pub struct ScreeningResponse {
+    pub blocked: bool,
+}
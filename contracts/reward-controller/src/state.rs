@@ -9,6 +9,32 @@ pub struct RewardConfig {
     pub paused: bool,
     pub claim_interval: u64,
     pub next_pool_id: u64,
+    /// Limits on admin-set parameters, adjustable by governance only
+    #[serde(default)]
+    pub bounds: ConfigBounds,
+    /// External contract asked about every staker and claimer, on top of `BLOCKLIST`
+    #[serde(default)]
+    pub screening_contract: Option<Addr>,
+}
+
+/// Hard limits that no configuration can exceed
+pub const MIN_CLAIM_INTERVAL: u64 = 60;
+pub const MAX_CLAIM_INTERVAL: u64 = 7 * 86_400;
+
+/// Configurable limits, always within the hard limits above
+#[cw_serde]
+pub struct ConfigBounds {
+    pub min_claim_interval: u64,
+    pub max_claim_interval: u64,
+}
+
+impl Default for ConfigBounds {
+    fn default() -> Self {
+        Self {
+            min_claim_interval: MIN_CLAIM_INTERVAL,
+            max_claim_interval: MAX_CLAIM_INTERVAL,
+        }
+    }
 }
 
 #[cw_serde]
@@ -50,8 +76,24 @@ pub struct UserReward {
     pub last_claim_time: u64,
 }
 
+/// One applied migration, kept for auditing upgrades
+#[cw_serde]
+pub struct MigrationRecord {
+    pub from_version: String,
+    pub to_version: String,
+    pub steps: Vec<String>,
+    pub height: u64,
+    pub time: u64,
+}
+
 pub const CONFIG: Item<RewardConfig> = Item::new("config");
 pub const POOLS: Map<u64, RewardPool> = Map::new("pools");
 pub const USER_STAKES: Map<(&Addr, u64), UserStake> = Map::new("user_stakes");
 pub const USER_REWARDS: Map<(&Addr, u64), UserReward> = Map::new("user_rewards");
 pub const TOTAL_STAKED: Item<Uint128> = Item::new("total_staked");
+/// User holding the stake of each registered locker
+pub const LOCKER_STAKERS: Map<u64, Addr> = Map::new("locker_stakers");
+/// Addresses barred from registering stakes and claiming rewards
+pub const BLOCKLIST: Map<&Addr, bool> = Map::new("blocklist");
+/// Migration history keyed by a sequence number, in the order migrations ran
+pub const MIGRATION_HISTORY: Map<u64, MigrationRecord> = Map::new("migration_history");
@@ -1,8 +1,589 @@
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn test_instantiate() {
-        // TODO: Add comprehensive tests
-        assert_eq!(1, 1);
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, ContractResult, Decimal, MemoryStorage, OwnedDeps,
+    SystemResult, Uint128, WasmQuery,
+};
+use serde_json::json;
+
+use crate::contract::{execute, instantiate, migrate, query, sudo};
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, LockerQueryMsg, MigrateMsg, QueryMsg,
+    ScreeningQueryMsg, ScreeningResponse, SudoMsg, UserStakeResponse,
+};
+use crate::state::{
+    AssetInfo, ConfigBounds, MigrationRecord, UserStake, LOCKER_STAKERS, TOTAL_STAKED, USER_STAKES,
+};
+
+const ADMIN: &str = "admin";
+const USER: &str = "user";
+const OPERATOR: &str = "operator";
+const LOCKER: &str = "lp_locker";
+const DAY: u64 = 86_400;
+
+type MockDeps = OwnedDeps<MemoryStorage, MockApi, MockQuerier>;
+
+/// Instantiates the controller with an lp-locker mock serving a single locker
+fn setup(locker: serde_json::Value) -> MockDeps {
+    let mut deps = mock_dependencies();
+    serve_locker(&mut deps, locker);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        InstantiateMsg {
+            admin: ADMIN.to_string(),
+            lp_locker_contract: LOCKER.to_string(),
+            claim_interval: None,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+/// Replaces the locker served by the lp-locker mock
+fn serve_locker(deps: &mut MockDeps, locker: serde_json::Value) {
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == LOCKER => {
+            let response = match from_json(msg).unwrap() {
+                LockerQueryMsg::Locker { .. } => locker.clone(),
+                LockerQueryMsg::WhitelistedLP { lp_token } => json!({
+                    "lp_token": lp_token,
+                    "min_lock_duration": DAY,
+                    "max_lock_duration": 365 * DAY,
+                    "enabled": true,
+                    "bonus_multiplier": "1.5",
+                }),
+                // Only OPERATOR is approved, and only for reward registration
+                LockerQueryMsg::Operator { operator, .. } => json!({
+                    "owner": USER,
+                    "operator": operator,
+                    "permissions": if operator == OPERATOR { vec!["register_rewards"] } else { vec![] },
+                    "expires": { "never": {} },
+                }),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+}
+
+fn locker_json(owner: &str, unlock_in: u64, auto_relock: Option<u64>) -> serde_json::Value {
+    let now = mock_env().block.time.seconds();
+    json!({
+        "id": 0,
+        "owner": owner,
+        "lp_token": "lp_token",
+        "amount": "1000",
+        "locked_at": now,
+        "unlock_time": now + unlock_in,
+        "extended_count": 0,
+        "emergency_unlock_requested": null,
+        "metadata": null,
+        "auto_relock": auto_relock,
+    })
+}
+
+fn query_stake(deps: &MockDeps, locker_id: u64) -> UserStakeResponse {
+    from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserStake {
+                user: USER.to_string(),
+                locker_id,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_instantiate() {
+    let deps = setup(locker_json(USER, 30 * DAY, None));
+
+    let config: crate::msg::ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.admin, ADMIN);
+    assert_eq!(config.lp_locker_contract, LOCKER);
+    assert_eq!(config.next_pool_id, 0);
+    assert!(!config.paused);
+}
+
+#[test]
+fn register_stake_uses_locker_details() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+
+    let stake = query_stake(&deps, 0);
+    assert_eq!(stake.lp_amount, Uint128::new(1000));
+    assert_eq!(stake.lock_duration, 30 * DAY);
+    assert_eq!(stake.bonus_multiplier, Decimal::percent(150));
+}
+
+#[test]
+fn register_stake_prefers_locker_tier_multiplier() {
+    let mut locker = locker_json(USER, 120 * DAY, None);
+    locker["bonus_multiplier"] = json!("2");
+    let mut deps = setup(locker);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+
+    assert_eq!(query_stake(&deps, 0).bonus_multiplier, Decimal::percent(200));
+}
+
+#[test]
+fn register_stake_counts_auto_relock_duration() {
+    let mut deps = setup(locker_json(USER, 90 * DAY, Some(90 * DAY)));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+
+    assert_eq!(query_stake(&deps, 0).lock_duration, 90 * DAY);
+}
+
+#[test]
+fn register_stake_again_refreshes_instead_of_double_counting() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    for _ in 0..2 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::RegisterStake { locker_id: 0 },
+        )
+        .unwrap();
+    }
+
+    let total = crate::state::TOTAL_STAKED.load(deps.as_ref().storage).unwrap();
+    assert_eq!(total, Uint128::new(1000));
+}
+
+#[test]
+fn register_stake_moves_stake_of_transferred_locker() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+    let register = ExecuteMsg::RegisterStake { locker_id: 0 };
+
+    execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), register.clone()).unwrap();
+
+    // The locker changed hands on the lp-locker
+    serve_locker(&mut deps, locker_json("heir", 30 * DAY, None));
+    execute(deps.as_mut(), mock_env(), mock_info("heir", &[]), register).unwrap();
+
+    let previous = QueryMsg::UserStake {
+        user: USER.to_string(),
+        locker_id: 0,
+    };
+    assert!(query(deps.as_ref(), mock_env(), previous).is_err());
+    assert_eq!(TOTAL_STAKED.load(deps.as_ref().storage).unwrap(), Uint128::new(1000));
+}
+
+#[test]
+fn locker_transferred_drops_the_previous_owners_stake() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+    let transferred = ExecuteMsg::LockerTransferred { locker_id: 0 };
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), transferred.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), mock_env(), mock_info(LOCKER, &[]), transferred.clone()).unwrap();
+    assert!(USER_STAKES
+        .may_load(deps.as_ref().storage, (&Addr::unchecked(USER), 0))
+        .unwrap()
+        .is_none());
+    assert_eq!(TOTAL_STAKED.load(deps.as_ref().storage).unwrap(), Uint128::zero());
+
+    // Lockers without a stake are ignored
+    execute(deps.as_mut(), mock_env(), mock_info(LOCKER, &[]), transferred).unwrap();
+}
+
+#[test]
+fn locker_updates_refresh_or_drop_auto_relock_stakes() {
+    let mut deps = setup(locker_json(USER, 0, Some(365 * DAY)));
+    let register = ExecuteMsg::RegisterStake { locker_id: 0 };
+    let updated = ExecuteMsg::LockerUpdated { locker_id: 0 };
+
+    execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), register.clone()).unwrap();
+    assert_eq!(query_stake(&deps, 0).lock_duration, 365 * DAY);
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), updated.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Disabling auto-relock starts a countdown the stake now follows
+    serve_locker(&mut deps, locker_json(USER, 30 * DAY, None));
+    execute(deps.as_mut(), mock_env(), mock_info(LOCKER, &[]), updated.clone()).unwrap();
+    assert_eq!(query_stake(&deps, 0).lock_duration, 30 * DAY);
+    assert_eq!(TOTAL_STAKED.load(deps.as_ref().storage).unwrap(), Uint128::new(1000));
+
+    // Matured lockers stop earning
+    serve_locker(&mut deps, locker_json(USER, 0, None));
+    execute(deps.as_mut(), mock_env(), mock_info(LOCKER, &[]), updated).unwrap();
+    assert!(USER_STAKES
+        .may_load(deps.as_ref().storage, (&Addr::unchecked(USER), 0))
+        .unwrap()
+        .is_none());
+    assert_eq!(TOTAL_STAKED.load(deps.as_ref().storage).unwrap(), Uint128::zero());
+}
+
+#[test]
+fn lockers_closed_drops_their_stakes() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+
+    // Lockers without a stake are ignored
+    let closed = ExecuteMsg::LockersClosed { locker_ids: vec![0, 7] };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), closed.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info(LOCKER, &[]), closed).unwrap();
+
+    assert!(LOCKER_STAKERS.may_load(deps.as_ref().storage, 0).unwrap().is_none());
+    assert_eq!(TOTAL_STAKED.load(deps.as_ref().storage).unwrap(), Uint128::zero());
+}
+
+#[test]
+fn register_stake_rejects_foreign_locker() {
+    let mut deps = setup(locker_json("someone_else", 30 * DAY, None));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLocker {});
+}
+
+#[test]
+fn register_stake_accepts_approved_operator() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OPERATOR, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+
+    // The stake belongs to the locker owner, not the operator
+    assert_eq!(query_stake(&deps, 0).lp_amount, Uint128::new(1000));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLocker {});
+}
+
+#[test]
+fn stake_and_pool_changes_emit_typed_events() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "reward_controller.stake_registered");
+    let attr = |key: &str| event.attributes.iter().find(|a| a.key == key).unwrap().value.clone();
+    assert_eq!(attr("user"), USER);
+    assert_eq!(attr("lp_amount"), "1000");
+    assert_eq!(attr("lock_duration"), (30 * DAY).to_string());
+    assert_eq!(attr("bonus_multiplier"), "1.5");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::CreateRewardPool {
+            reward_token: AssetInfo::Native("ureward".to_string()),
+            emission_per_second: Uint128::new(10),
+            start_time: 0,
+            end_time: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "reward_controller.pool_created");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &coins(500, "ureward")),
+        ExecuteMsg::DepositRewards { pool_id: 0 },
+    )
+    .unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "reward_controller.pool_funded");
+    assert!(event.attributes.iter().any(|a| a.key == "total_deposited" && a.value == "500"));
+}
+
+#[test]
+fn blocked_users_cannot_register_or_claim() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateBlocklist {
+            add: vec![USER.to_string()],
+            remove: vec![],
+        },
+    )
+    .unwrap();
+
+    let blocked = ContractError::Blocked {
+        address: USER.to_string(),
+    };
+    let register = ExecuteMsg::RegisterStake { locker_id: 0 };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), register).unwrap_err();
+    assert_eq!(err, blocked);
+
+    // Removed from the blocklist but flagged by the screening contract
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateBlocklist {
+            add: vec![],
+            remove: vec![USER.to_string()],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetScreening {
+            contract: Some("screening".to_string()),
+        },
+    )
+    .unwrap();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "screening" => {
+            let ScreeningQueryMsg::IsBlocked { address } = from_json(msg).unwrap();
+            let response = ScreeningResponse {
+                blocked: address == USER,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let claim = ExecuteMsg::ClaimRewards { pool_ids: vec![0] };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), claim).unwrap_err();
+    assert_eq!(err, blocked);
+
+    let res: ScreeningResponse = from_json(
+        query(deps.as_ref(), mock_env(), QueryMsg::IsBlocked { address: USER.to_string() })
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(!res.blocked);
+}
+
+#[test]
+fn sudo_replaces_admin_and_pauses() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    let new_admin = SudoMsg::UpdateAdmin {
+        admin: "council".to_string(),
+    };
+    sudo(deps.as_mut(), mock_env(), new_admin).unwrap();
+    sudo(deps.as_mut(), mock_env(), SudoMsg::ForcePause {}).unwrap();
+
+    let config: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.admin, Addr::unchecked("council"));
+    assert!(config.paused);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER, &[]),
+        ExecuteMsg::RegisterStake { locker_id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+}
+
+#[test]
+fn claim_interval_stays_within_bounds() {
+    let mut deps = setup(locker_json(USER, 30 * DAY, None));
+
+    let update = |interval: u64| ExecuteMsg::UpdateConfig {
+        admin: None,
+        lp_locker_contract: None,
+        claim_interval: Some(interval),
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(30 * DAY))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidClaimInterval { min: 60, max: 7 * DAY });
+
+    // Claims can never be allowed back to back
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(0)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidClaimInterval { min: 60, max: 7 * DAY });
+
+    let bounds = ConfigBounds {
+        min_claim_interval: 600,
+        max_claim_interval: DAY,
+    };
+    sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateBounds { bounds }).unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(60)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidClaimInterval { min: 600, max: DAY });
+
+    for (min, max) in [(0, DAY), (60, 8 * DAY)] {
+        let bounds = ConfigBounds {
+            min_claim_interval: min,
+            max_claim_interval: max,
+        };
+        let err = sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateBounds { bounds }).unwrap_err();
+        assert_eq!(err, ContractError::InvalidBounds {});
+    }
+}
+
+/// Storage layout written by reward-controller 2.0.0
+mod v2_0_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_storage_plus::Item;
+
+    #[cw_serde]
+    pub struct RewardConfig {
+        pub admin: Addr,
+        pub lp_locker_contract: Addr,
+        pub paused: bool,
+        pub claim_interval: u64,
+        pub next_pool_id: u64,
     }
+
+    pub const CONFIG: Item<RewardConfig> = Item::new("config");
+    pub const TOTAL_STAKED: Item<Uint128> = Item::new("total_staked");
+}
+
+fn v2_0_0_state() -> MockDeps {
+    let mut deps = mock_dependencies();
+    let storage = deps.as_mut().storage;
+
+    cw2::set_contract_version(storage, "crates.io:reward-controller", "2.0.0").unwrap();
+    v2_0_0::CONFIG
+        .save(
+            storage,
+            &v2_0_0::RewardConfig {
+                admin: Addr::unchecked(ADMIN),
+                lp_locker_contract: Addr::unchecked(LOCKER),
+                paused: false,
+                claim_interval: 3600,
+                next_pool_id: 0,
+            },
+        )
+        .unwrap();
+    v2_0_0::TOTAL_STAKED.save(storage, &Uint128::zero()).unwrap();
+
+    deps
+}
+
+#[test]
+fn migrate_from_v2_0_0_records_history() {
+    let mut deps = v2_0_0_state();
+    let stake = UserStake {
+        user: Addr::unchecked(USER),
+        locker_id: 7,
+        lp_amount: Uint128::new(1000),
+        lock_start: 0,
+        lock_duration: 30 * DAY,
+        bonus_multiplier: Decimal::one(),
+    };
+    USER_STAKES
+        .save(deps.as_mut().storage, (&Addr::unchecked(USER), 7), &stake)
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let config: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.claim_interval, 3600);
+
+    let history: Vec<MigrationRecord> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MigrationHistory {}).unwrap())
+            .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].from_version, "2.0.0");
+    assert_eq!(history[0].to_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(history[0].steps, vec!["index_locker_stakers".to_string()]);
+
+    // A second migration in the same block gets its own record
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let history: Vec<MigrationRecord> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MigrationHistory {}).unwrap())
+            .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].height, history[0].height);
+
+    let staker = LOCKER_STAKERS.load(deps.as_ref().storage, 7).unwrap();
+    assert_eq!(staker, Addr::unchecked(USER));
+}
+
+#[test]
+fn migrate_rejects_downgrade() {
+    let mut deps = v2_0_0_state();
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:reward-controller", "9.0.0")
+        .unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MigrationDowngrade {
+            from: "9.0.0".to_string(),
+            to: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
 }
@@ -0,0 +1,10 @@
+//! Typed events emitted by the LP Platform contracts.
+//!
+//! Each struct converts into a `cosmwasm_std::Event` whose type is
+//! `<contract>.<name>`, e.g. `lp_locker.locked`; the chain reports it as
+//! `wasm-lp_locker.locked`. The attribute keys are a stable schema for
+//! indexers: new keys may be added, existing keys are never renamed or removed.
+//! Optional values are always present and empty when unset.
+
+pub mod locker;
+pub mod rewards;
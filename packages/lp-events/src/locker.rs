@@ -0,0 +1,193 @@
+//! Events of the lp-locker contract
+
+use cosmwasm_std::{Addr, Event, Uint128};
+
+const PREFIX: &str = "lp_locker";
+
+fn event(name: &str, locker_id: u64, lp_token: &Addr) -> Event {
+    Event::new(format!("{PREFIX}.{name}"))
+        .add_attribute("locker_id", locker_id.to_string())
+        .add_attribute("lp_token", lp_token)
+}
+
+/// A new locker was created
+pub struct Locked {
+    pub locker_id: u64,
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub unlock_time: u64,
+    pub auto_relock: Option<u64>,
+}
+
+impl From<Locked> for Event {
+    fn from(e: Locked) -> Self {
+        event("locked", e.locker_id, &e.lp_token)
+            .add_attribute("owner", e.owner)
+            .add_attribute("amount", e.amount)
+            .add_attribute("unlock_time", e.unlock_time.to_string())
+            .add_attribute("auto_relock", e.auto_relock.map_or(String::new(), |d| d.to_string()))
+    }
+}
+
+/// LP was added to an existing locker
+pub struct ToppedUp {
+    pub locker_id: u64,
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub new_amount: Uint128,
+}
+
+impl From<ToppedUp> for Event {
+    fn from(e: ToppedUp) -> Self {
+        event("topped_up", e.locker_id, &e.lp_token)
+            .add_attribute("owner", e.owner)
+            .add_attribute("amount", e.amount)
+            .add_attribute("new_amount", e.new_amount)
+    }
+}
+
+/// The unlock time moved later
+pub struct Extended {
+    pub locker_id: u64,
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub old_unlock_time: u64,
+    pub new_unlock_time: u64,
+}
+
+impl From<Extended> for Event {
+    fn from(e: Extended) -> Self {
+        event("extended", e.locker_id, &e.lp_token)
+            .add_attribute("owner", e.owner)
+            .add_attribute("old_unlock_time", e.old_unlock_time.to_string())
+            .add_attribute("new_unlock_time", e.new_unlock_time.to_string())
+    }
+}
+
+/// Auto-relock was switched on (`duration` set) or off (`duration` empty).
+/// Switching off fixes the unlock time.
+pub struct AutoRelockChanged {
+    pub locker_id: u64,
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub duration: Option<u64>,
+    pub unlock_time: u64,
+}
+
+impl From<AutoRelockChanged> for Event {
+    fn from(e: AutoRelockChanged) -> Self {
+        event("auto_relock_changed", e.locker_id, &e.lp_token)
+            .add_attribute("owner", e.owner)
+            .add_attribute("duration", e.duration.map_or(String::new(), |d| d.to_string()))
+            .add_attribute("unlock_time", e.unlock_time.to_string())
+    }
+}
+
+/// An emergency unlock was requested (`execute_at` set) or withdrawn
+/// (`execute_at` empty)
+pub struct EmergencyChanged {
+    pub locker_id: u64,
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub execute_at: Option<u64>,
+}
+
+impl From<EmergencyChanged> for Event {
+    fn from(e: EmergencyChanged) -> Self {
+        event("emergency_changed", e.locker_id, &e.lp_token)
+            .add_attribute("owner", e.owner)
+            .add_attribute("amount", e.amount)
+            .add_attribute("execute_at", e.execute_at.map_or(String::new(), |t| t.to_string()))
+    }
+}
+
+/// Ownership moved; any pending emergency request was dropped
+pub struct Transferred {
+    pub locker_id: u64,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub from: Addr,
+    pub to: Addr,
+}
+
+impl From<Transferred> for Event {
+    fn from(e: Transferred) -> Self {
+        event("transferred", e.locker_id, &e.lp_token)
+            .add_attribute("amount", e.amount)
+            .add_attribute("from", e.from)
+            .add_attribute("to", e.to)
+    }
+}
+
+/// An inactive owner's locker passed to their beneficiary, lock terms unchanged
+pub struct Inherited {
+    pub locker_id: u64,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub from: Addr,
+    pub to: Addr,
+}
+
+impl From<Inherited> for Event {
+    fn from(e: Inherited) -> Self {
+        event("inherited", e.locker_id, &e.lp_token)
+            .add_attribute("amount", e.amount)
+            .add_attribute("from", e.from)
+            .add_attribute("to", e.to)
+    }
+}
+
+/// How a locker was released
+pub enum UnlockKind {
+    /// By its owner after the unlock time
+    Matured,
+    /// By its owner after the emergency delay
+    Emergency,
+    /// By a keeper after the unlock time
+    Keeper,
+    /// By chain governance
+    Forced,
+    /// By the admin, to the recovery address, because the owner is blocked
+    Recovered,
+}
+
+impl UnlockKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnlockKind::Matured => "matured",
+            UnlockKind::Emergency => "emergency",
+            UnlockKind::Keeper => "keeper",
+            UnlockKind::Forced => "forced",
+            UnlockKind::Recovered => "recovered",
+        }
+    }
+}
+
+/// A locker was closed and its LP released to `recipient`: the owner, or the
+/// recovery address for `Recovered`
+pub struct Unlocked {
+    pub locker_id: u64,
+    pub owner: Addr,
+    pub recipient: Addr,
+    pub lp_token: Addr,
+    pub amount: Uint128,
+    pub unlock_time: u64,
+    pub kind: UnlockKind,
+    /// Paid to the keeper from platform funds on top of `amount`
+    pub tip: Uint128,
+}
+
+impl From<Unlocked> for Event {
+    fn from(e: Unlocked) -> Self {
+        event("unlocked", e.locker_id, &e.lp_token)
+            .add_attribute("owner", e.owner)
+            .add_attribute("recipient", e.recipient)
+            .add_attribute("amount", e.amount)
+            .add_attribute("unlock_time", e.unlock_time.to_string())
+            .add_attribute("kind", e.kind.as_str())
+            .add_attribute("tip", e.tip)
+    }
+}
@@ -0,0 +1,98 @@
+//! Events of the reward-controller contract
+
+use cosmwasm_std::{Addr, Decimal, Event, Uint128};
+
+const PREFIX: &str = "reward_controller";
+
+fn event(name: &str) -> Event {
+    Event::new(format!("{PREFIX}.{name}"))
+}
+
+/// A locker was registered, or re-registered, for rewards
+pub struct StakeRegistered {
+    pub user: Addr,
+    pub locker_id: u64,
+    pub lp_amount: Uint128,
+    pub lock_duration: u64,
+    pub bonus_multiplier: Decimal,
+}
+
+impl From<StakeRegistered> for Event {
+    fn from(e: StakeRegistered) -> Self {
+        event("stake_registered")
+            .add_attribute("user", e.user)
+            .add_attribute("locker_id", e.locker_id.to_string())
+            .add_attribute("lp_amount", e.lp_amount)
+            .add_attribute("lock_duration", e.lock_duration.to_string())
+            .add_attribute("bonus_multiplier", e.bonus_multiplier.to_string())
+    }
+}
+
+pub struct StakeUnregistered {
+    pub user: Addr,
+    pub locker_id: u64,
+    pub lp_amount: Uint128,
+}
+
+impl From<StakeUnregistered> for Event {
+    fn from(e: StakeUnregistered) -> Self {
+        event("stake_unregistered")
+            .add_attribute("user", e.user)
+            .add_attribute("locker_id", e.locker_id.to_string())
+            .add_attribute("lp_amount", e.lp_amount)
+    }
+}
+
+/// Rewards of one pool paid to `user`
+pub struct RewardsClaimed {
+    pub user: Addr,
+    pub pool_id: u64,
+    pub amount: Uint128,
+}
+
+impl From<RewardsClaimed> for Event {
+    fn from(e: RewardsClaimed) -> Self {
+        event("rewards_claimed")
+            .add_attribute("user", e.user)
+            .add_attribute("pool_id", e.pool_id.to_string())
+            .add_attribute("amount", e.amount)
+    }
+}
+
+pub struct PoolCreated {
+    pub pool_id: u64,
+    /// CW20 address or native denom
+    pub reward_token: String,
+    pub emission_per_second: Uint128,
+    pub start_time: u64,
+    pub end_time: Option<u64>,
+}
+
+impl From<PoolCreated> for Event {
+    fn from(e: PoolCreated) -> Self {
+        event("pool_created")
+            .add_attribute("pool_id", e.pool_id.to_string())
+            .add_attribute("reward_token", e.reward_token)
+            .add_attribute("emission_per_second", e.emission_per_second)
+            .add_attribute("start_time", e.start_time.to_string())
+            .add_attribute("end_time", e.end_time.map_or(String::new(), |t| t.to_string()))
+    }
+}
+
+/// Reward funds were deposited into or withdrawn from a pool
+pub struct PoolFunded {
+    pub pool_id: u64,
+    pub deposited: Uint128,
+    pub withdrawn: Uint128,
+    pub total_deposited: Uint128,
+}
+
+impl From<PoolFunded> for Event {
+    fn from(e: PoolFunded) -> Self {
+        event("pool_funded")
+            .add_attribute("pool_id", e.pool_id.to_string())
+            .add_attribute("deposited", e.deposited)
+            .add_attribute("withdrawn", e.withdrawn)
+            .add_attribute("total_deposited", e.total_deposited)
+    }
+}